@@ -0,0 +1,479 @@
+use anyhow::{anyhow, Context as _, Result};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+// ============================================================================
+// STORAGE BACKEND - Pluggable byte persistence behind the storage traits
+// ============================================================================
+//
+// `FractalStorage` ties node/contribution bytes to one local filesystem path
+// (`FractalStorage::new("./rust-fractal-storage")`). This abstracts the
+// concrete byte persistence behind a `StorageBackend` trait keyed by
+// namespaced object keys (`nodes/{id}.json`, `contributions/{hash}.json`,
+// `manifest.json`), with local-file, embedded SQLite, and S3-compatible
+// implementations selected via `StorageConfig::backend`. Like `httpsign.rs` hand-verifies
+// HTTP Signatures instead of depending on an ActivityPub crate, `S3Backend`
+// hand-signs plain `reqwest` calls with AWS Signature Version 4 rather than
+// pulling in `aws-sdk-s3`, so it works against AWS, MinIO, R2, or anything
+// else that speaks the S3 API.
+
+pub trait StorageBackend {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()>;
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+    async fn list(&self, prefix: &str) -> Result<Vec<String>>;
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub enum BackendConfig {
+    Local {
+        base_path: String,
+    },
+    /// Embedded SQLite, one table per namespace (`nodes`, `contributions`, ...).
+    Sqlite {
+        db_path: String,
+    },
+    S3 {
+        bucket: String,
+        prefix: String,
+        region: String,
+        /// Custom endpoint for S3-compatible stores (MinIO, R2, ...);
+        /// defaults to `https://s3.{region}.amazonaws.com` when unset.
+        endpoint: Option<String>,
+    },
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        BackendConfig::Local { base_path: "./rust-fractal-storage".to_string() }
+    }
+}
+
+impl BackendConfig {
+    /// Whether this backend's bytes can survive this node's local disk being
+    /// wiped (a redeploy, a fresh container). `Local` lives under
+    /// `StorageConfig::base_path`, on the same disk as the oplog, so it can't;
+    /// `Sqlite`/`S3` point at a path or bucket the deployer controls
+    /// independently. `FractalStorage`'s recovery order prefers this backend
+    /// over the oplog exactly when this is true, since a redeployed node may
+    /// have lost the oplog but not the backend.
+    pub fn may_outlive_local_disk(&self) -> bool {
+        !matches!(self, BackendConfig::Local { .. })
+    }
+}
+
+/// Selects the concrete backend at runtime; avoids `dyn StorageBackend`
+/// (the trait's native `async fn`s aren't object-safe) the same way the rest
+/// of this crate favors concrete dispatch over trait objects.
+pub enum ObjectBackend {
+    Local(LocalFileBackend),
+    Sqlite(SqliteBackend),
+    S3(S3Backend),
+}
+
+impl ObjectBackend {
+    pub fn from_config(config: &BackendConfig) -> Result<Self> {
+        match config {
+            BackendConfig::Local { base_path } => Ok(ObjectBackend::Local(LocalFileBackend::new(base_path))),
+            BackendConfig::Sqlite { db_path } => Ok(ObjectBackend::Sqlite(SqliteBackend::open(db_path)?)),
+            BackendConfig::S3 { bucket, prefix, region, endpoint } => Ok(ObjectBackend::S3(S3Backend::from_env(
+                bucket.clone(),
+                prefix.clone(),
+                region.clone(),
+                endpoint.clone(),
+            )?)),
+        }
+    }
+}
+
+impl StorageBackend for ObjectBackend {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        match self {
+            ObjectBackend::Local(backend) => backend.put(key, bytes).await,
+            ObjectBackend::Sqlite(backend) => backend.put(key, bytes).await,
+            ObjectBackend::S3(backend) => backend.put(key, bytes).await,
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match self {
+            ObjectBackend::Local(backend) => backend.get(key).await,
+            ObjectBackend::Sqlite(backend) => backend.get(key).await,
+            ObjectBackend::S3(backend) => backend.get(key).await,
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        match self {
+            ObjectBackend::Local(backend) => backend.list(prefix).await,
+            ObjectBackend::Sqlite(backend) => backend.list(prefix).await,
+            ObjectBackend::S3(backend) => backend.list(prefix).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self {
+            ObjectBackend::Local(backend) => backend.delete(key).await,
+            ObjectBackend::Sqlite(backend) => backend.delete(key).await,
+            ObjectBackend::S3(backend) => backend.delete(key).await,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Local filesystem backend - one file per key under `base_path`
+// ----------------------------------------------------------------------------
+
+pub struct LocalFileBackend {
+    base_path: PathBuf,
+}
+
+impl LocalFileBackend {
+    pub fn new(base_path: &str) -> Self {
+        Self { base_path: PathBuf::from(base_path) }
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        self.base_path.join(key)
+    }
+}
+
+impl StorageBackend for LocalFileBackend {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let path = self.path_for(key);
+        tokio::task::spawn_blocking(move || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, bytes).with_context(|| format!("writing {}", path.display()))
+        })
+        .await
+        .context("put task panicked")?
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path_for(key);
+        tokio::task::spawn_blocking(move || match std::fs::read(&path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        })
+        .await
+        .context("get task panicked")?
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = self.path_for(prefix);
+        let prefix = prefix.trim_end_matches('/').to_string();
+        tokio::task::spawn_blocking(move || {
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(e) => return Err(anyhow::Error::from(e)),
+            };
+            let mut keys = Vec::new();
+            for entry in entries {
+                let entry = entry?;
+                if entry.file_type()?.is_file() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        keys.push(format!("{}/{}", prefix, name));
+                    }
+                }
+            }
+            Ok(keys)
+        })
+        .await
+        .context("list task panicked")?
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path_for(key);
+        tokio::task::spawn_blocking(move || match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        })
+        .await
+        .context("delete task panicked")?
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Embedded SQLite backend - one table per namespace, in a single file
+// ----------------------------------------------------------------------------
+//
+// Keys are namespaced like `nodes/codex:Void.json`; the namespace (the part
+// before the first `/`) picks the table, the rest is the row key.
+
+pub struct SqliteBackend {
+    conn: std::sync::Arc<std::sync::Mutex<rusqlite::Connection>>,
+}
+
+impl SqliteBackend {
+    pub fn open(db_path: &str) -> Result<Self> {
+        let conn = rusqlite::Connection::open(db_path).with_context(|| format!("opening sqlite db {}", db_path))?;
+        Ok(Self { conn: std::sync::Arc::new(std::sync::Mutex::new(conn)) })
+    }
+}
+
+/// Split a namespaced key like `nodes/codex:Void.json` into its table
+/// namespace and the remaining row key (`codex:Void.json`).
+fn split_namespace(key: &str) -> (String, String) {
+    match key.split_once('/') {
+        Some((namespace, rest)) => (namespace.to_string(), rest.to_string()),
+        None => ("default".to_string(), key.to_string()),
+    }
+}
+
+/// Sanitize a namespace into a safe SQLite table identifier.
+fn table_name(namespace: &str) -> String {
+    let sanitized: String = namespace.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    format!("ns_{}", sanitized)
+}
+
+impl StorageBackend for SqliteBackend {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let conn = self.conn.clone();
+        let (namespace, row_key) = split_namespace(key);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().expect("sqlite connection mutex poisoned");
+            let table = table_name(&namespace);
+            conn.execute(&format!("CREATE TABLE IF NOT EXISTS {} (key TEXT PRIMARY KEY, value BLOB NOT NULL)", table), [])?;
+            conn.execute(&format!("INSERT OR REPLACE INTO {} (key, value) VALUES (?1, ?2)", table), rusqlite::params![row_key, bytes])?;
+            Ok(())
+        })
+        .await
+        .context("sqlite put task panicked")?
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let conn = self.conn.clone();
+        let (namespace, row_key) = split_namespace(key);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().expect("sqlite connection mutex poisoned");
+            let table = table_name(&namespace);
+            conn.execute(&format!("CREATE TABLE IF NOT EXISTS {} (key TEXT PRIMARY KEY, value BLOB NOT NULL)", table), [])?;
+            match conn.query_row(&format!("SELECT value FROM {} WHERE key = ?1", table), rusqlite::params![row_key], |row| row.get::<_, Vec<u8>>(0)) {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+        .await
+        .context("sqlite get task panicked")?
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let conn = self.conn.clone();
+        // A bare namespace like `nodes` names that whole table, not a row
+        // prefix inside the `default` table.
+        let (namespace, row_prefix) = match prefix.split_once('/') {
+            Some(_) => split_namespace(prefix),
+            None => (prefix.to_string(), String::new()),
+        };
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().expect("sqlite connection mutex poisoned");
+            let table = table_name(&namespace);
+            conn.execute(&format!("CREATE TABLE IF NOT EXISTS {} (key TEXT PRIMARY KEY, value BLOB NOT NULL)", table), [])?;
+            let mut statement = conn.prepare(&format!("SELECT key FROM {} WHERE key LIKE ?1", table))?;
+            let pattern = format!("{}%", row_prefix);
+            let rows = statement.query_map(rusqlite::params![pattern], |row| row.get::<_, String>(0))?;
+            let mut keys = Vec::new();
+            for row in rows {
+                keys.push(format!("{}/{}", namespace, row?));
+            }
+            Ok(keys)
+        })
+        .await
+        .context("sqlite list task panicked")?
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let conn = self.conn.clone();
+        let (namespace, row_key) = split_namespace(key);
+        tokio::task::spawn_blocking(move || {
+            let conn = conn.lock().expect("sqlite connection mutex poisoned");
+            let table = table_name(&namespace);
+            conn.execute(&format!("CREATE TABLE IF NOT EXISTS {} (key TEXT PRIMARY KEY, value BLOB NOT NULL)", table), [])?;
+            conn.execute(&format!("DELETE FROM {} WHERE key = ?1", table), rusqlite::params![row_key])?;
+            Ok(())
+        })
+        .await
+        .context("sqlite delete task panicked")?
+    }
+}
+
+// ----------------------------------------------------------------------------
+// S3-compatible backend - signs requests with AWS Signature Version 4
+// ----------------------------------------------------------------------------
+
+pub struct S3Backend {
+    bucket: String,
+    prefix: String,
+    region: String,
+    endpoint: String,
+    access_key: String,
+    secret_key: String,
+    client: reqwest::Client,
+}
+
+impl S3Backend {
+    pub fn from_env(bucket: String, prefix: String, region: String, endpoint: Option<String>) -> Result<Self> {
+        let access_key = std::env::var("AWS_ACCESS_KEY_ID").context("AWS_ACCESS_KEY_ID not set")?;
+        let secret_key = std::env::var("AWS_SECRET_ACCESS_KEY").context("AWS_SECRET_ACCESS_KEY not set")?;
+        let endpoint = endpoint.unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", region));
+        Ok(Self { bucket, prefix, region, endpoint, access_key, secret_key, client: reqwest::Client::new() })
+    }
+
+    fn object_key(&self, key: &str) -> String {
+        format!("{}/{}", self.prefix.trim_end_matches('/'), key)
+    }
+
+    fn host(&self) -> String {
+        self.endpoint.trim_start_matches("https://").trim_start_matches("http://").to_string()
+    }
+
+    /// Sign and send a single path-style S3 request (`{endpoint}/{bucket}/{object_key}`,
+    /// or `{endpoint}/{bucket}` with an empty `object_key` for bucket-level
+    /// operations like `ListObjectsV2`) using AWS Signature Version 4.
+    async fn signed_request(
+        &self,
+        method: reqwest::Method,
+        object_key: &str,
+        query: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::Response> {
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+        let host = self.host();
+
+        let canonical_uri =
+            if object_key.is_empty() { format!("/{}", self.bucket) } else { format!("/{}/{}", self.bucket, object_key) };
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let canonical_headers = format!("host:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n", host, payload_hash, amz_date);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+
+        let canonical_request =
+            format!("{}\n{}\n{}\n{}\n{}\n{}", method.as_str(), canonical_uri, query, canonical_headers, signed_headers, payload_hash);
+
+        let credential_scope = format!("{}/{}/s3/aws4_request", date_stamp, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            credential_scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = sigv4_signing_key(&self.secret_key, &date_stamp, &self.region, "s3");
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, credential_scope, signed_headers, signature
+        );
+
+        let mut url = format!("{}{}", self.endpoint.trim_end_matches('/'), canonical_uri);
+        if !query.is_empty() {
+            url = format!("{}?{}", url, query);
+        }
+
+        self.client
+            .request(method, &url)
+            .header("host", host)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 request failed: {}", e))
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sigv4_signing_key(secret_key: &str, date_stamp: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret_key).as_bytes(), date_stamp.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+impl StorageBackend for S3Backend {
+    async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let object_key = self.object_key(key);
+        let response = self.signed_request(reqwest::Method::PUT, &object_key, "", bytes).await?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(anyhow!("S3 PUT {} failed: {}", object_key, response.status()))
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let object_key = self.object_key(key);
+        let response = self.signed_request(reqwest::Method::GET, &object_key, "", Vec::new()).await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(anyhow!("S3 GET {} failed: {}", object_key, response.status()));
+        }
+        Ok(Some(response.bytes().await?.to_vec()))
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let full_prefix = self.object_key(prefix);
+        let query = format!("list-type=2&prefix={}", urlencode(&full_prefix));
+        let response = self.signed_request(reqwest::Method::GET, "", &query, Vec::new()).await?;
+        if !response.status().is_success() {
+            return Err(anyhow!("S3 ListObjectsV2 failed: {}", response.status()));
+        }
+        let body = response.text().await?;
+        // Return keys relative to `self.prefix`, the form `get`/`delete` take.
+        let base = format!("{}/", self.prefix.trim_end_matches('/'));
+        Ok(parse_list_objects_keys(&body).into_iter().map(|key| key.strip_prefix(&base).map(String::from).unwrap_or(key)).collect())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let object_key = self.object_key(key);
+        let response = self.signed_request(reqwest::Method::DELETE, &object_key, "", Vec::new()).await?;
+        if response.status().is_success() || response.status() == reqwest::StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
+            Err(anyhow!("S3 DELETE {} failed: {}", object_key, response.status()))
+        }
+    }
+}
+
+fn urlencode(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~' | '/') { c.to_string() } else { format!("%{:02X}", c as u32) })
+        .collect()
+}
+
+/// Extract `<Key>...</Key>` entries from a `ListObjectsV2` XML response,
+/// without pulling in a full XML parser for one tag.
+fn parse_list_objects_keys(xml: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<Key>") {
+        let after_start = &rest[start + "<Key>".len()..];
+        if let Some(end) = after_start.find("</Key>") {
+            keys.push(after_start[..end].to_string());
+            rest = &after_start[end + "</Key>".len()..];
+        } else {
+            break;
+        }
+    }
+    keys
+}
@@ -0,0 +1,258 @@
+use crate::backend::{BackendConfig, ObjectBackend, StorageBackend};
+use crate::cluster::NodeRole;
+use crate::models::{Contribution, FractalNode};
+use crate::ServerConfig;
+use anyhow::{Context as _, Result};
+use clap::{Parser, Subcommand};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+// ============================================================================
+// CLI + CONFIG - `serve` (default) and `migrate` subcommands
+// ============================================================================
+//
+// `ServerConfig` used to be built but never read: `main` hardcoded the bind
+// address, storage path, and CORS policy. This loads `config.toml`, applies
+// `--host`/`--port`/`--domain`/`--storage-path` overrides on top, and exposes
+// a `migrate` subcommand that upgrades the on-disk storage layout.
+
+#[derive(Parser, Debug)]
+#[command(name = "living-codex-fractal-federation", about = "Rust fractal federation server")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path to a TOML config file.
+    #[arg(short, long, global = true, default_value = "config.toml")]
+    pub config: PathBuf,
+
+    #[arg(long, global = true)]
+    pub host: Option<String>,
+
+    #[arg(long, global = true)]
+    pub port: Option<u16>,
+
+    /// Public hostname this server federates as, e.g. `https://codex.example.org`.
+    #[arg(long, global = true)]
+    pub domain: Option<String>,
+
+    #[arg(long = "storage-path", global = true)]
+    pub storage_path: Option<String>,
+
+    /// Run as an `ingest` node (accepts writes) or a `query` node (fans
+    /// reads out across a roster of ingest nodes). Defaults to `ingest`.
+    #[arg(long, global = true)]
+    pub role: Option<NodeRole>,
+
+    /// For an `ingest` node: the query node to register with on startup.
+    #[arg(long = "query-node", global = true)]
+    pub query_node: Option<String>,
+
+    /// Storage backend to use: `local` (default), `sqlite`, or `s3`. Per-kind
+    /// settings (db path, bucket, ...) come from `config.toml`'s `[backend]`
+    /// table.
+    #[arg(long, global = true)]
+    pub backend: Option<String>,
+}
+
+impl clap::ValueEnum for NodeRole {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[NodeRole::Ingest, NodeRole::Query]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        Some(match self {
+            NodeRole::Ingest => clap::builder::PossibleValue::new("ingest"),
+            NodeRole::Query => clap::builder::PossibleValue::new("query"),
+        })
+    }
+}
+
+#[derive(Subcommand, Debug, Default)]
+pub enum Command {
+    /// Run the federation server (default if no subcommand is given).
+    #[default]
+    Serve,
+    /// Initialize or upgrade the on-disk fractal storage layout.
+    Migrate,
+}
+
+/// The subset of `ServerConfig` that may appear in `config.toml`; every field
+/// is optional so a partial file only overrides what it sets.
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    host: Option<String>,
+    port: Option<u16>,
+    storage_path: Option<String>,
+    enable_cors: Option<bool>,
+    log_level: Option<String>,
+    fractal_levels: Option<Vec<u32>>,
+    peers: Option<Vec<String>>,
+    domain: Option<String>,
+    role: Option<String>,
+    query_node: Option<String>,
+    backend: Option<BackendConfigFile>,
+}
+
+/// The `[backend]` table in `config.toml`, selecting `StorageConfig::backend`.
+/// `kind` picks the `BackendConfig` variant; the rest are that variant's
+/// fields, so only the ones the chosen `kind` needs must be set.
+#[derive(Debug, Default, Deserialize)]
+struct BackendConfigFile {
+    kind: Option<String>,
+    base_path: Option<String>,
+    db_path: Option<String>,
+    bucket: Option<String>,
+    prefix: Option<String>,
+    region: Option<String>,
+    endpoint: Option<String>,
+}
+
+/// Build a `BackendConfig` from `kind` and whatever `[backend]` fields
+/// `config.toml` set, defaulting per-kind paths to live under `storage_path`
+/// alongside the oplog.
+fn resolve_backend(kind: &str, file: &BackendConfigFile, storage_path: &str) -> Result<BackendConfig> {
+    match kind {
+        "local" => Ok(BackendConfig::Local { base_path: file.base_path.clone().unwrap_or_else(|| storage_path.to_string()) }),
+        "sqlite" => {
+            let db_path = file.db_path.clone().unwrap_or_else(|| format!("{}/storage.sqlite3", storage_path));
+            Ok(BackendConfig::Sqlite { db_path })
+        }
+        "s3" => Ok(BackendConfig::S3 {
+            bucket: file.bucket.clone().ok_or_else(|| anyhow::anyhow!("backend.bucket is required when backend kind is \"s3\""))?,
+            prefix: file.prefix.clone().unwrap_or_default(),
+            region: file.region.clone().unwrap_or_else(|| "us-east-1".to_string()),
+            endpoint: file.endpoint.clone(),
+        }),
+        other => Err(anyhow::anyhow!("unknown backend kind \"{}\" (expected local, sqlite, or s3)", other)),
+    }
+}
+
+/// Load `config.toml` (if present) over `ServerConfig::default()`, then apply
+/// CLI flag overrides on top.
+pub fn load_config(cli: &Cli) -> Result<ServerConfig> {
+    let mut config = ServerConfig::default();
+    let mut backend_kind: Option<String> = None;
+    let mut backend_file = BackendConfigFile::default();
+
+    if cli.config.exists() {
+        apply_file(&mut config, &mut backend_kind, &mut backend_file, &cli.config)?;
+    } else {
+        info!("no config file at {}, using defaults", cli.config.display());
+    }
+
+    if let Some(host) = &cli.host {
+        config.host = host.clone();
+    }
+    if let Some(port) = cli.port {
+        config.port = port;
+    }
+    if let Some(domain) = &cli.domain {
+        config.domain = domain.clone();
+    }
+    if let Some(storage_path) = &cli.storage_path {
+        config.storage_path = storage_path.clone();
+    }
+    if let Some(role) = cli.role {
+        config.role = role;
+    }
+    if let Some(query_node) = &cli.query_node {
+        config.query_node = Some(query_node.clone());
+    }
+    if let Some(backend) = &cli.backend {
+        backend_kind = Some(backend.clone());
+    }
+
+    let kind = backend_kind.unwrap_or_else(|| "local".to_string());
+    config.backend = resolve_backend(&kind, &backend_file, &config.storage_path)?;
+
+    Ok(config)
+}
+
+fn apply_file(config: &mut ServerConfig, backend_kind: &mut Option<String>, backend_file: &mut BackendConfigFile, path: &Path) -> Result<()> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading config file {}", path.display()))?;
+    let file: ConfigFile = toml::from_str(&text).with_context(|| format!("parsing config file {}", path.display()))?;
+
+    if let Some(host) = file.host {
+        config.host = host;
+    }
+    if let Some(port) = file.port {
+        config.port = port;
+    }
+    if let Some(storage_path) = file.storage_path {
+        config.storage_path = storage_path;
+    }
+    if let Some(enable_cors) = file.enable_cors {
+        config.enable_cors = enable_cors;
+    }
+    if let Some(log_level) = file.log_level {
+        config.log_level = log_level;
+    }
+    if let Some(fractal_levels) = file.fractal_levels {
+        config.fractal_levels = fractal_levels;
+    }
+    if let Some(peers) = file.peers {
+        config.peers = peers;
+    }
+    if let Some(domain) = file.domain {
+        config.domain = domain;
+    }
+    if let Some(role) = file.role {
+        config.role = role.parse().map_err(anyhow::Error::msg)?;
+    }
+    if let Some(query_node) = file.query_node {
+        config.query_node = Some(query_node);
+    }
+    if let Some(backend) = file.backend {
+        if let Some(kind) = &backend.kind {
+            *backend_kind = Some(kind.clone());
+        }
+        *backend_file = backend;
+    }
+
+    Ok(())
+}
+
+/// Initialize the on-disk storage layout if missing, then rewrite every
+/// persisted node/contribution through `schema::load_versioned` so it's
+/// upgraded to `schema::CURRENT_SCHEMA_VERSION` on disk instead of only in
+/// memory the next time something happens to load it.
+pub async fn run_migrate(config: &ServerConfig) -> Result<()> {
+    info!("migrating storage at {}", config.storage_path);
+    let path = Path::new(&config.storage_path);
+    std::fs::create_dir_all(path)?;
+    for dir in ["nodes", "contributions", "contexts"] {
+        std::fs::create_dir_all(path.join(dir))?;
+    }
+
+    let backend = ObjectBackend::from_config(&config.backend)?;
+    let mut rewritten_count = 0usize;
+
+    for key in backend.list("nodes").await? {
+        if let Some(bytes) = backend.get(&key).await? {
+            let node: FractalNode = crate::schema::load_versioned(&bytes)
+                .with_context(|| format!("migrating node at {}", key))?;
+            let rewritten = serde_json::to_vec(&node)?;
+            if rewritten != bytes {
+                backend.put(&key, rewritten).await?;
+                rewritten_count += 1;
+            }
+        }
+    }
+
+    for key in backend.list("contributions").await? {
+        if let Some(bytes) = backend.get(&key).await? {
+            let contribution: Contribution = crate::schema::load_versioned(&bytes)
+                .with_context(|| format!("migrating contribution at {}", key))?;
+            let rewritten = serde_json::to_vec(&contribution)?;
+            if rewritten != bytes {
+                backend.put(&key, rewritten).await?;
+                rewritten_count += 1;
+            }
+        }
+    }
+
+    info!("migrate: rewrote {} record(s) at schema version {}", rewritten_count, crate::schema::CURRENT_SCHEMA_VERSION);
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use crate::models::{Contribution, FractalNode, StorageStats};
+use crate::storage::FractalStorage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+// ============================================================================
+// CLUSTER ROLES - Ingest/query split for horizontal scaling
+// ============================================================================
+//
+// An `ingest` node accepts writes (`/inbox`, the GraphQL mutation) and
+// persists them locally, same as a single-node deployment always has. A
+// `query` node accepts no writes; instead it fans out reads across the
+// ingest nodes registered in its roster and merges the results, so a read
+// scales with however many ingest nodes are behind it rather than one disk.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeRole {
+    #[default]
+    Ingest,
+    Query,
+}
+
+impl std::str::FromStr for NodeRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ingest" => Ok(NodeRole::Ingest),
+            "query" => Ok(NodeRole::Query),
+            other => Err(format!("unknown node role '{}', expected 'ingest' or 'query'", other)),
+        }
+    }
+}
+
+/// An ingest node as seen by a query node's roster.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestNodeInfo {
+    pub url: String,
+    pub capabilities: Vec<String>,
+    pub fractal_levels: Vec<u32>,
+    pub registered_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterRequest {
+    pub url: String,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    #[serde(default)]
+    pub fractal_levels: Vec<u32>,
+}
+
+/// Live roster of ingest nodes, maintained by a query node via
+/// `POST /cluster/register` and pruned by a periodic health check.
+pub struct ClusterRoster {
+    nodes: tokio::sync::RwLock<HashMap<String, IngestNodeInfo>>,
+}
+
+impl ClusterRoster {
+    pub fn new() -> Self {
+        Self { nodes: tokio::sync::RwLock::new(HashMap::new()) }
+    }
+
+    pub async fn register(&self, req: RegisterRequest) {
+        let now = Utc::now();
+        let mut nodes = self.nodes.write().await;
+        nodes
+            .entry(req.url.clone())
+            .and_modify(|existing| {
+                existing.capabilities = req.capabilities.clone();
+                existing.fractal_levels = req.fractal_levels.clone();
+                existing.last_seen = now;
+            })
+            .or_insert(IngestNodeInfo {
+                url: req.url,
+                capabilities: req.capabilities,
+                fractal_levels: req.fractal_levels,
+                registered_at: now,
+                last_seen: now,
+            });
+    }
+
+    pub async fn snapshot(&self) -> Vec<IngestNodeInfo> {
+        self.nodes.read().await.values().cloned().collect()
+    }
+
+    async fn mark_seen(&self, url: &str) {
+        if let Some(node) = self.nodes.write().await.get_mut(url) {
+            node.last_seen = Utc::now();
+        }
+    }
+
+    async fn remove(&self, url: &str) {
+        self.nodes.write().await.remove(url);
+    }
+}
+
+impl Default for ClusterRoster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Called once at startup by an ingest node to join a query node's roster.
+pub async fn register_with_query_node(
+    query_node: &str,
+    self_url: &str,
+    capabilities: Vec<String>,
+    fractal_levels: Vec<u32>,
+) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/cluster/register", query_node.trim_end_matches('/')))
+        .json(&json!({ "url": self_url, "capabilities": capabilities, "fractal_levels": fractal_levels }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("query node rejected registration: {}", response.status()));
+    }
+
+    info!("registered with query node {}", query_node);
+    Ok(())
+}
+
+/// Periodically pings every roster entry's `/health` and drops it if
+/// unreachable, so a dead ingest node doesn't keep getting fanned out to.
+pub fn spawn_health_checks(roster: Arc<ClusterRoster>, interval: Duration) {
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        loop {
+            tokio::time::sleep(interval).await;
+            for node in roster.snapshot().await {
+                let health_url = format!("{}/health", node.url.trim_end_matches('/'));
+                match client.get(&health_url).send().await {
+                    Ok(resp) if resp.status().is_success() => roster.mark_seen(&node.url).await,
+                    _ => {
+                        warn!("cluster: dropping unreachable ingest node {}", node.url);
+                        roster.remove(&node.url).await;
+                    }
+                }
+            }
+        }
+    });
+}
+
+// ============================================================================
+// READ AGGREGATION - Fan reads out across the roster and merge the results
+// ============================================================================
+
+pub async fn aggregate_storage_stats(roster: &ClusterRoster) -> StorageStats {
+    let client = reqwest::Client::new();
+    let mut combined = StorageStats { total_nodes: 0, total_subnodes: 0, total_contributions: 0, total_size: 0, ..StorageStats::default() };
+
+    for node in roster.snapshot().await {
+        let url = format!("{}/storage/stats", node.url.trim_end_matches('/'));
+        let Ok(response) = client.get(&url).send().await else { continue };
+        let Ok(stats) = response.json::<StorageStats>().await else { continue };
+
+        combined.total_nodes += stats.total_nodes;
+        combined.total_subnodes += stats.total_subnodes;
+        combined.total_contributions += stats.total_contributions;
+        combined.total_size += stats.total_size;
+        if stats.last_updated > combined.last_updated {
+            combined.last_updated = stats.last_updated;
+        }
+    }
+
+    combined
+}
+
+/// Fetch every page of an ingest node's paginated `/contributions/node/:id`
+/// (or `/contributions/user/:id`) endpoint and merge across every roster
+/// entry, deduplicating by `content_hash` (computed with `local`'s hasher) so
+/// a query node doesn't report the same contribution twice. Follows `total_pages`
+/// rather than stopping after the first page, since a node can hold far more
+/// than one page's worth of contributions.
+pub async fn aggregate_contributions(
+    roster: &ClusterRoster,
+    local: &FractalStorage,
+    path_for: impl Fn(&str) -> String,
+) -> Value {
+    let client = reqwest::Client::new();
+    let mut merged: Vec<Contribution> = Vec::new();
+    let mut seen_hashes: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+    for node in roster.snapshot().await {
+        let base_url = format!("{}{}", node.url.trim_end_matches('/'), path_for(&node.url));
+        let mut page = 0usize;
+
+        loop {
+            let url = format!("{}?page={}", base_url, page);
+            let Ok(response) = client.get(&url).send().await else { break };
+            let Ok(body) = response.json::<Value>().await else { break };
+            let Some(items) = body.get("items").and_then(|v| v.as_array()) else { break };
+
+            for item in items {
+                let Ok(contribution) = serde_json::from_value::<Contribution>(item.clone()) else { continue };
+                let hash = local.generate_content_hash(&contribution.content);
+                if seen_hashes.insert(hash) {
+                    merged.push(contribution);
+                }
+            }
+
+            let total_pages = body.get("total_pages").and_then(|v| v.as_u64()).unwrap_or(1);
+            page += 1;
+            if (page as u64) >= total_pages {
+                break;
+            }
+        }
+    }
+
+    let count = merged.len();
+    json!({ "contributions": merged, "count": count })
+}
+
+/// Merge one context's nodes across every roster entry's
+/// `/fractal/context/:context`, deduplicating by node id.
+pub async fn aggregate_fractal_context(roster: &ClusterRoster, context: &str) -> Vec<FractalNode> {
+    let client = reqwest::Client::new();
+    let mut merged: HashMap<String, FractalNode> = HashMap::new();
+
+    for node in roster.snapshot().await {
+        let url = format!("{}/fractal/context/{}", node.url.trim_end_matches('/'), context);
+        let Ok(response) = client.get(&url).send().await else { continue };
+        let Ok(body) = response.json::<Value>().await else { continue };
+        let Some(nodes) = body.get("nodes").and_then(|v| v.as_array()) else { continue };
+
+        for item in nodes {
+            if let Ok(fractal_node) = serde_json::from_value::<FractalNode>(item.clone()) {
+                merged.insert(fractal_node.id.clone(), fractal_node);
+            }
+        }
+    }
+
+    merged.into_values().collect()
+}
@@ -0,0 +1,216 @@
+use crate::httpsign;
+use crate::models::Contribution;
+use crate::storage::{ContributionStorage, FractalStorage, StoreOutcome};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+// ============================================================================
+// FEDERATION SYNC - Real outbound pull/push between Codex peers
+// ============================================================================
+//
+// `get_federation_peers`/`federation_sync` used to return hardcoded JSON.
+// This performs an actual pull from each peer's outbox, deduplicates by
+// `content_hash` against local storage, and pushes our own recent
+// contributions to each peer's inbox as ActivityPub `Create` activities.
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerSyncResult {
+    pub peer: String,
+    pub pulled: usize,
+    pub pushed: usize,
+    pub skipped_duplicate: usize,
+    pub errors: Vec<String>,
+}
+
+impl PeerSyncResult {
+    fn new(peer: &str) -> Self {
+        Self { peer: peer.to_string(), pulled: 0, pushed: 0, skipped_duplicate: 0, errors: Vec::new() }
+    }
+}
+
+/// Sync with a single peer: pull new contributions from its outbox, then
+/// push our own recent contributions to its inbox.
+pub async fn sync_peer(
+    storage: &Arc<FractalStorage>,
+    peer_url: &str,
+    domain: &str,
+    signing_key: &httpsign::SigningKey,
+) -> PeerSyncResult {
+    let mut result = PeerSyncResult::new(peer_url);
+
+    pull_from_peer(storage, peer_url, &mut result).await;
+    push_to_peer(storage, peer_url, domain, signing_key, &mut result).await;
+
+    result
+}
+
+async fn pull_from_peer(storage: &Arc<FractalStorage>, peer_url: &str, result: &mut PeerSyncResult) {
+    let base_url = peer_url.trim_end_matches('/');
+    let outbox_url = format!("{}/outbox", base_url);
+    let response = match reqwest::get(&outbox_url).await {
+        Ok(r) => r,
+        Err(e) => {
+            result.errors.push(format!("failed to fetch {}: {}", outbox_url, e));
+            return;
+        }
+    };
+
+    let collection: Value = match response.json().await {
+        Ok(v) => v,
+        Err(e) => {
+            result.errors.push(format!("invalid outbox response from {}: {}", outbox_url, e));
+            return;
+        }
+    };
+
+    // `/outbox` is a paginated `OrderedCollection`: its items are on the
+    // `OrderedCollectionPage`s reached from `first` and then each page's `next`.
+    let resolve = |link: &str| if link.starts_with("http") { link.to_string() } else { format!("{}{}", base_url, link) };
+    let mut items = collection.get("orderedItems").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    let mut next_page = collection.get("first").and_then(|v| v.as_str()).map(resolve);
+    while let Some(page_url) = next_page.take() {
+        let page: Value = match reqwest::get(&page_url).await {
+            Ok(response) => response.json().await.unwrap_or_default(),
+            Err(e) => {
+                result.errors.push(format!("failed to fetch {}: {}", page_url, e));
+                break;
+            }
+        };
+        items.extend(page.get("orderedItems").and_then(|v| v.as_array()).cloned().unwrap_or_default());
+        next_page = page.get("next").and_then(|v| v.as_str()).map(resolve);
+    }
+
+    for item in items {
+        let object = item.get("object").unwrap_or(&item);
+        let node_id = object.get("nodeId").or_else(|| object.get("node_id")).and_then(|v| v.as_str());
+        let content = object.get("content").and_then(|v| v.as_str());
+        let user_id = object.get("userId").or_else(|| object.get("user_id")).and_then(|v| v.as_str()).unwrap_or("federated");
+        let resonance = object.get("resonance").and_then(|v| v.as_f64()).unwrap_or(0.5);
+
+        let (Some(node_id), Some(content)) = (node_id, content) else {
+            result.errors.push("outbox item missing nodeId/content".to_string());
+            continue;
+        };
+
+        let content_hash = storage.generate_content_hash(content);
+        if storage.get_contribution(&content_hash).await.is_some() {
+            result.skipped_duplicate += 1;
+            continue;
+        }
+
+        let contribution = Contribution::new(node_id.to_string(), user_id.to_string(), content.to_string(), resonance, None);
+        match storage.store_contribution(contribution).await {
+            Ok(StoreOutcome::Stored) | Ok(StoreOutcome::Evicted { .. }) => result.pulled += 1,
+            Ok(StoreOutcome::RejectedBelowThreshold) => {
+                result.errors.push("pulled contribution rejected: below resonance threshold".to_string())
+            }
+            Err(e) => result.errors.push(format!("failed to store pulled contribution: {}", e)),
+        }
+    }
+}
+
+/// The path `verify_request` on the receiving end signs/checks against;
+/// kept separate from `inbox_url` since the signing string covers only the
+/// path, not the full URL.
+const INBOX_PATH: &str = "/inbox";
+
+async fn push_to_peer(
+    storage: &Arc<FractalStorage>,
+    peer_url: &str,
+    domain: &str,
+    signing_key: &httpsign::SigningKey,
+    result: &mut PeerSyncResult,
+) {
+    let inbox_url = format!("{}{}", peer_url.trim_end_matches('/'), INBOX_PATH);
+    let client = reqwest::Client::new();
+
+    let host = match reqwest::Url::parse(&inbox_url) {
+        Ok(url) => match url.host_str() {
+            Some(host) => match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            },
+            None => {
+                result.errors.push(format!("peer URL has no host: {}", peer_url));
+                return;
+            }
+        },
+        Err(e) => {
+            result.errors.push(format!("invalid peer URL {}: {}", peer_url, e));
+            return;
+        }
+    };
+
+    // Push a bounded recent window rather than the whole history each sync.
+    let recent = storage.all_contributions().await.into_iter().take(50);
+
+    for contribution in recent {
+        let activity = json!({
+            "type": "Create",
+            "actor": format!("{}/actor", domain),
+            "object": {
+                "nodeId": contribution.node_id,
+                "content": contribution.content,
+                "resonance": contribution.resonance,
+                "userId": contribution.user_id,
+            }
+        });
+
+        let body = match serde_json::to_vec(&activity) {
+            Ok(body) => body,
+            Err(e) => {
+                result.errors.push(format!("failed to encode push activity: {}", e));
+                continue;
+            }
+        };
+
+        // `post_to_inbox` on the receiving end requires a signed `Digest`
+        // covering the body for any request with one; sign the same four
+        // headers it reconstructs from the `Signature` header it receives.
+        let date = chrono::Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+        let digest = httpsign::compute_digest(&body);
+        let signature = match signing_key.sign_request("POST", INBOX_PATH, &host, &date, &digest) {
+            Ok(signature) => signature,
+            Err(e) => {
+                result.errors.push(format!("failed to sign push to {}: {}", inbox_url, e));
+                continue;
+            }
+        };
+
+        let request = client
+            .post(&inbox_url)
+            .header("Host", &host)
+            .header("Date", &date)
+            .header("Digest", &digest)
+            .header("Signature", &signature)
+            .header("Content-Type", "application/activity+json")
+            .body(body);
+
+        match request.send().await {
+            Ok(resp) if resp.status().is_success() => result.pushed += 1,
+            Ok(resp) => result.errors.push(format!("{} rejected push: {}", inbox_url, resp.status())),
+            Err(e) => result.errors.push(format!("failed to push to {}: {}", inbox_url, e)),
+        }
+    }
+}
+
+/// Sync with every configured peer, returning per-peer results.
+pub async fn sync_all_peers(
+    storage: &Arc<FractalStorage>,
+    peers: &[String],
+    domain: &str,
+    signing_key: &httpsign::SigningKey,
+) -> Vec<PeerSyncResult> {
+    let mut results = Vec::with_capacity(peers.len());
+    for peer in peers {
+        info!("federation: syncing with peer {}", peer);
+        let result = sync_peer(storage, peer, domain, signing_key).await;
+        if !result.errors.is_empty() {
+            warn!("federation: peer {} sync had {} error(s)", peer, result.errors.len());
+        }
+        results.push(result);
+    }
+    results
+}
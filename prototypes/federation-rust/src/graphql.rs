@@ -0,0 +1,296 @@
+use crate::cluster::NodeRole;
+use crate::models::{Contribution, ContextType, EvolutionStage, FlowState, FractalNode, Resonant, Transformable};
+use crate::storage::{ContributionStorage, FractalStorage, NodeStorage, StorageMetadata};
+use crate::storage::StoreOutcome;
+use crate::ServerConfig;
+use async_graphql::{Context, Enum, Object, Schema, SimpleObject, Subscription};
+use futures_util::{Stream, StreamExt};
+use std::sync::Arc;
+use tokio_stream::wrappers::BroadcastStream;
+
+// ============================================================================
+// GRAPHQL API - async-graphql mounted alongside the existing REST routes
+// ============================================================================
+//
+// Shares the same `Arc<FractalStorage>` state as the REST handlers. Queries
+// read fractal nodes/contributions, the mutation reuses the same
+// `Contribution::new` + `store_contribution` path REST uses, and the
+// subscription streams `FractalStorage`'s contribution broadcast channel so
+// clients can tail the Codex live instead of polling `/storage/stats`.
+
+pub type CodexSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+pub fn build_schema(storage: Arc<FractalStorage>, config: Arc<ServerConfig>) -> CodexSchema {
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot).data(storage).data(config).finish()
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum FlowStateGql {
+    Solid,
+    Liquid,
+    Gas,
+    Plasma,
+    Colloidal,
+    Crystalline,
+    Living,
+    Wave,
+}
+
+impl From<&FlowState> for FlowStateGql {
+    fn from(state: &FlowState) -> Self {
+        match state {
+            FlowState::Solid => FlowStateGql::Solid,
+            FlowState::Liquid => FlowStateGql::Liquid,
+            FlowState::Gas => FlowStateGql::Gas,
+            FlowState::Plasma => FlowStateGql::Plasma,
+            FlowState::Colloidal => FlowStateGql::Colloidal,
+            FlowState::Crystalline => FlowStateGql::Crystalline,
+            FlowState::Living => FlowStateGql::Living,
+            FlowState::Wave => FlowStateGql::Wave,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum EvolutionStageGql {
+    Potential,
+    Emerging,
+    Mature,
+    Transforming,
+    Transcending,
+}
+
+impl From<EvolutionStage> for EvolutionStageGql {
+    fn from(stage: EvolutionStage) -> Self {
+        match stage {
+            EvolutionStage::Potential => EvolutionStageGql::Potential,
+            EvolutionStage::Emerging => EvolutionStageGql::Emerging,
+            EvolutionStage::Mature => EvolutionStageGql::Mature,
+            EvolutionStage::Transforming => EvolutionStageGql::Transforming,
+            EvolutionStage::Transcending => EvolutionStageGql::Transcending,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ContextKindGql {
+    Scientific,
+    Symbolic,
+    Water,
+    Hybrid,
+}
+
+impl From<&ContextType> for ContextKindGql {
+    fn from(context: &ContextType) -> Self {
+        match context {
+            ContextType::Scientific(_) => ContextKindGql::Scientific,
+            ContextType::Symbolic(_) => ContextKindGql::Symbolic,
+            ContextType::Water(_) => ContextKindGql::Water,
+            ContextType::Hybrid(_) => ContextKindGql::Hybrid,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct NodeGql {
+    pub id: String,
+    pub name: String,
+    pub water_state: FlowStateGql,
+    pub archetype: Vec<String>,
+    pub resonance: f64,
+    pub fractal_level: u32,
+    pub evolution_stage: EvolutionStageGql,
+    pub contexts: Vec<ContextKindGql>,
+    pub parent_id: Option<String>,
+}
+
+impl From<FractalNode> for NodeGql {
+    fn from(n: FractalNode) -> Self {
+        let water_state = FlowStateGql::from(&n.water_state);
+        let evolution_stage = EvolutionStageGql::from(n.evolution_stage());
+        let contexts = n.contexts.iter().map(ContextKindGql::from).collect();
+        Self {
+            id: n.id,
+            name: n.name,
+            water_state,
+            archetype: n.archetype,
+            resonance: n.resonance,
+            fractal_level: n.fractal_level,
+            evolution_stage,
+            contexts,
+            parent_id: n.parent_id,
+        }
+    }
+}
+
+/// A neighbour a node can resonate with, per `Resonant::can_resonate_with`'s
+/// 10.0 frequency-cutoff contract, paired with `Resonant::resonance_strength`.
+#[derive(SimpleObject, Clone)]
+pub struct ResonanceEdgeGql {
+    pub node: NodeGql,
+    pub resonance_strength: f64,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct StorageStatsGql {
+    pub version: String,
+    pub total_nodes: usize,
+    pub total_contributions: usize,
+    pub total_users: usize,
+}
+
+impl From<crate::models::StorageStats> for StorageStatsGql {
+    fn from(stats: crate::models::StorageStats) -> Self {
+        Self {
+            version: stats.version,
+            total_nodes: stats.total_nodes,
+            total_contributions: stats.total_contributions,
+            total_users: stats.total_users,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct ContributionGql {
+    pub id: String,
+    pub node_id: String,
+    pub user_id: String,
+    pub content: String,
+    pub resonance: f64,
+}
+
+impl From<Contribution> for ContributionGql {
+    fn from(c: Contribution) -> Self {
+        Self { id: c.id, node_id: c.node_id, user_id: c.user_id, content: c.content, resonance: c.resonance }
+    }
+}
+
+fn context_matches(node: &FractalNode, context: &str) -> bool {
+    node.contexts.iter().any(|c| match c {
+        ContextType::Scientific(_) => context == "scientific",
+        ContextType::Symbolic(_) => context == "symbolic",
+        ContextType::Water(_) => context == "water",
+        ContextType::Hybrid(_) => context == "hybrid",
+    })
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// A single fractal node by id.
+    async fn node(&self, ctx: &Context<'_>, id: String) -> Option<NodeGql> {
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        storage.get_node(&id).await.map(NodeGql::from)
+    }
+
+    /// Fractal nodes, optionally filtered by context lens or fractal level.
+    async fn nodes(&self, ctx: &Context<'_>, context: Option<String>, fractal_level: Option<u32>) -> Vec<NodeGql> {
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        storage
+            .get_all_nodes()
+            .await
+            .into_iter()
+            .filter(|n| context.as_deref().map(|c| context_matches(n, c)).unwrap_or(true))
+            .filter(|n| fractal_level.map(|level| n.fractal_level == level).unwrap_or(true))
+            .map(NodeGql::from)
+            .collect()
+    }
+
+    /// Direct children of `parent_id`.
+    async fn children(&self, ctx: &Context<'_>, parent_id: String) -> Vec<NodeGql> {
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        storage
+            .get_all_nodes()
+            .await
+            .into_iter()
+            .filter(|n| n.parent_id.as_deref() == Some(parent_id.as_str()))
+            .map(NodeGql::from)
+            .collect()
+    }
+
+    /// Neighbours `id` can resonate with, per `Resonant::can_resonate_with`,
+    /// paired with their `resonance_strength`.
+    async fn resonates_with(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<Vec<ResonanceEdgeGql>> {
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        let node = storage.get_node(&id).await.ok_or_else(|| async_graphql::Error::new(format!("node not found: {}", id)))?;
+
+        Ok(storage
+            .get_all_nodes()
+            .await
+            .into_iter()
+            .filter(|other| other.id != node.id && node.can_resonate_with(other))
+            .map(|other| {
+                let resonance_strength = node.resonance_strength(&other);
+                ResonanceEdgeGql { node: NodeGql::from(other), resonance_strength }
+            })
+            .collect())
+    }
+
+    /// Storage-wide totals of this node's own storage. Unlike the REST
+    /// `/storage/stats` route, not aggregated across the roster on a query node.
+    async fn storage_stats(&self, ctx: &Context<'_>) -> StorageStatsGql {
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        StorageStatsGql::from(storage.get_storage_stats().await)
+    }
+
+    /// Contributions, optionally filtered by node or user.
+    async fn contributions(&self, ctx: &Context<'_>, node_id: Option<String>, user_id: Option<String>) -> Vec<ContributionGql> {
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        let value = if let Some(node_id) = node_id {
+            storage.get_node_contributions(&node_id).await
+        } else if let Some(user_id) = user_id {
+            storage.get_user_contributions(&user_id).await
+        } else {
+            return storage.all_contributions().await.into_iter().map(ContributionGql::from).collect();
+        };
+        serde_json::from_value::<Vec<Contribution>>(value.get("contributions").cloned().unwrap_or_default())
+            .unwrap_or_default()
+            .into_iter()
+            .map(ContributionGql::from)
+            .collect()
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    /// Submit a contribution, reusing the same path REST and federation use.
+    async fn submit_contribution(
+        &self,
+        ctx: &Context<'_>,
+        node_id: String,
+        user_id: String,
+        content: String,
+        resonance: f64,
+    ) -> async_graphql::Result<ContributionGql> {
+        let config = ctx.data_unchecked::<Arc<ServerConfig>>();
+        if config.role == NodeRole::Query {
+            return Err(async_graphql::Error::new("writes are not accepted on a query node"));
+        }
+
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        let contribution = Contribution::new(node_id, user_id, content, resonance, None);
+        let stored = contribution.clone();
+        match storage.store_contribution(contribution).await.map_err(|e| async_graphql::Error::new(e.to_string()))? {
+            StoreOutcome::Stored | StoreOutcome::Evicted { .. } => Ok(ContributionGql::from(stored)),
+            StoreOutcome::RejectedBelowThreshold => {
+                Err(async_graphql::Error::new("contribution resonance is below the configured threshold"))
+            }
+        }
+    }
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Streams every contribution accepted from now on, across all ingest paths.
+    async fn contribution_stored(&self, ctx: &Context<'_>) -> impl Stream<Item = ContributionGql> {
+        let storage = ctx.data_unchecked::<Arc<FractalStorage>>();
+        let receiver = storage.subscribe_contributions();
+        BroadcastStream::new(receiver).filter_map(|result| async move { result.ok().map(ContributionGql::from) })
+    }
+}
+
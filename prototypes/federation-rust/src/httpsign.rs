@@ -0,0 +1,231 @@
+use anyhow::{anyhow, Result};
+use axum::http::HeaderMap;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use rand::rngs::OsRng;
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+use rsa::{Pkcs1v15Sign, RsaPrivateKey, RsaPublicKey};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tracing::warn;
+
+// ============================================================================
+// HTTP SIGNATURES - Verifying inbound ActivityPub activities
+// ============================================================================
+//
+// `post_to_inbox` used to trust whatever `actor` the JSON body claimed. This
+// module reconstructs and verifies the `Signature` header the way mature
+// Fediverse servers do: fetch the actor's public key, rebuild the signing
+// string from the listed headers, and check the RSA-SHA256 signature. The
+// verified key owner - not the body - becomes the authenticated actor.
+
+#[derive(Debug, Clone)]
+pub struct ParsedSignature {
+    pub key_id: String,
+    pub headers: Vec<String>,
+    pub signature: Vec<u8>,
+}
+
+/// Parse a `Signature: keyId="...",headers="...",signature="..."` header.
+pub fn parse_signature_header(value: &str) -> Result<ParsedSignature> {
+    let mut key_id = None;
+    let mut headers = None;
+    let mut signature = None;
+
+    for part in split_signature_params(value) {
+        let (name, raw_value) = part
+            .split_once('=')
+            .ok_or_else(|| anyhow!("malformed Signature parameter: {}", part))?;
+        let unquoted = raw_value.trim_matches('"');
+        match name.trim() {
+            "keyId" => key_id = Some(unquoted.to_string()),
+            "headers" => headers = Some(unquoted.split_whitespace().map(String::from).collect()),
+            "signature" => signature = Some(BASE64.decode(unquoted)?),
+            _ => {} // "algorithm" and other params are accepted but not required
+        }
+    }
+
+    Ok(ParsedSignature {
+        key_id: key_id.ok_or_else(|| anyhow!("Signature header missing keyId"))?,
+        headers: headers.unwrap_or_else(|| vec!["(request-target)".to_string(), "host".to_string(), "date".to_string()]),
+        signature: signature.ok_or_else(|| anyhow!("Signature header missing signature"))?,
+    })
+}
+
+/// Split on commas that are outside quoted values, since `headers="a b c"` may
+/// itself contain no commas but other params could in principle.
+fn split_signature_params(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                parts.push(value[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(value[start..].trim());
+    parts.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+/// Reconstruct the exact string the sender signed, per the listed header names.
+pub fn build_signing_string(
+    method: &str,
+    path: &str,
+    headers: &HeaderMap,
+    listed_headers: &[String],
+) -> Result<String> {
+    let mut lines = Vec::with_capacity(listed_headers.len());
+    for name in listed_headers {
+        if name == "(request-target)" {
+            lines.push(format!("(request-target): {} {}", method.to_lowercase(), path));
+            continue;
+        }
+        let header_value = headers
+            .get(name.as_str())
+            .ok_or_else(|| anyhow!("signed header missing from request: {}", name))?
+            .to_str()
+            .map_err(|_| anyhow!("signed header is not valid UTF-8: {}", name))?;
+        lines.push(format!("{}: {}", name, header_value));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Base64 SHA-256 digest of the raw request body, in the `SHA-256=...` form
+/// used by the `Digest` header.
+pub fn compute_digest(body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(body);
+    format!("SHA-256={}", BASE64.encode(hasher.finalize()))
+}
+
+/// Fetch the actor document at `key_id` (the fragment-qualified actor URL)
+/// and extract its `publicKeyPem`.
+pub async fn fetch_public_key(key_id: &str) -> Result<RsaPublicKey> {
+    let actor_url = key_id.split('#').next().unwrap_or(key_id);
+    let response = reqwest::get(actor_url).await?.error_for_status()?;
+    let actor_doc: serde_json::Value = response.json().await?;
+
+    let pem = actor_doc
+        .get("publicKey")
+        .and_then(|k| k.get("publicKeyPem"))
+        .and_then(|p| p.as_str())
+        .ok_or_else(|| anyhow!("actor document at {} has no publicKeyPem", actor_url))?;
+
+    RsaPublicKey::from_public_key_pem(pem).map_err(|e| anyhow!("invalid publicKeyPem for {}: {}", actor_url, e))
+}
+
+fn verify_rsa_sha256(public_key: &RsaPublicKey, signing_string: &str, signature: &[u8]) -> bool {
+    let mut hasher = Sha256::new();
+    hasher.update(signing_string.as_bytes());
+    let digest = hasher.finalize();
+    public_key.verify(Pkcs1v15Sign::new::<Sha256>(), &digest, signature).is_ok()
+}
+
+/// Verify an inbound request end to end: parse the `Signature` header, fetch
+/// the actor's key, recompute the `Digest` header, and check the signature.
+/// Returns the verified actor id (the key owner) on success.
+pub async fn verify_request(headers: &HeaderMap, method: &str, path: &str, body: &[u8]) -> Result<String> {
+    let signature_header = headers
+        .get("signature")
+        .ok_or_else(|| anyhow!("missing Signature header"))?
+        .to_str()
+        .map_err(|_| anyhow!("Signature header is not valid UTF-8"))?;
+    let parsed = parse_signature_header(signature_header)?;
+
+    // Digest coverage is mandatory for any request with a body - an actor
+    // can't opt out of it by leaving "digest" out of their own `headers=`
+    // list, since that same list is also what `build_signing_string` signs
+    // over. Without this, an attacker picks a header list that omits
+    // "digest" and the signature never covers the body at all.
+    if !body.is_empty() {
+        if !parsed.headers.iter().any(|h| h == "digest") {
+            return Err(anyhow!("Signature must cover the Digest header for requests with a body"));
+        }
+
+        let expected_digest = compute_digest(body);
+        let provided_digest = headers
+            .get("digest")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| anyhow!("Digest header required but missing"))?;
+        if expected_digest != provided_digest {
+            warn!("digest mismatch for signed request from {}", parsed.key_id);
+            return Err(anyhow!("Digest header does not match body"));
+        }
+    }
+
+    let signing_string = build_signing_string(method, path, headers, &parsed.headers)?;
+    let public_key = fetch_public_key(&parsed.key_id).await?;
+
+    if !verify_rsa_sha256(&public_key, &signing_string, &parsed.signature) {
+        return Err(anyhow!("signature verification failed for {}", parsed.key_id));
+    }
+
+    // The actor is the key owner, i.e. the actor URL the keyId is scoped to,
+    // never whatever the (untrusted) request body claims.
+    Ok(parsed.key_id.split('#').next().unwrap_or(&parsed.key_id).to_string())
+}
+
+// ============================================================================
+// HTTP SIGNATURES - Signing outbound federation pushes
+// ============================================================================
+//
+// `verify_request` above checks that inbound activities are signed; this is
+// the other half - this node's own keypair, used so pushes this node makes
+// to a peer's `/inbox` (see `federation::push_to_peer`) pass that same check
+// there. Persisted to disk so the `keyId` advertised in `/actor` is stable
+// across restarts.
+
+/// This node's RSA keypair, used to sign outbound federation requests.
+pub struct SigningKey {
+    pub key_id: String,
+    private_key: RsaPrivateKey,
+}
+
+impl SigningKey {
+    /// Load the keypair persisted at `path`, generating and saving a new
+    /// 2048-bit RSA keypair if none exists yet.
+    pub fn load_or_generate(path: &Path, key_id: String) -> Result<Self> {
+        let private_key = if path.exists() {
+            let pem = std::fs::read_to_string(path)?;
+            RsaPrivateKey::from_pkcs8_pem(&pem).map_err(|e| anyhow!("invalid signing key at {}: {}", path.display(), e))?
+        } else {
+            let private_key = RsaPrivateKey::new(&mut OsRng, 2048)?;
+            let pem = private_key
+                .to_pkcs8_pem(LineEnding::LF)
+                .map_err(|e| anyhow!("failed to encode generated signing key: {}", e))?;
+            std::fs::write(path, pem.as_bytes())?;
+            private_key
+        };
+        Ok(Self { key_id, private_key })
+    }
+
+    /// PEM-encoded public key, published as `publicKey.publicKeyPem` in `/actor`
+    /// so peers can fetch it via `fetch_public_key` above.
+    pub fn public_key_pem(&self) -> Result<String> {
+        let public_key = RsaPublicKey::from(&self.private_key);
+        public_key.to_public_key_pem(LineEnding::LF).map_err(|e| anyhow!("failed to encode public key: {}", e))
+    }
+
+    /// Build the `Signature` header value for an outbound request, signing
+    /// `(request-target)`, `host`, `date`, and `digest` - the same set
+    /// `verify_request` requires of inbound requests with a body.
+    pub fn sign_request(&self, method: &str, path: &str, host: &str, date: &str, digest: &str) -> Result<String> {
+        let signing_string =
+            format!("(request-target): {} {}\nhost: {}\ndate: {}\ndigest: {}", method.to_lowercase(), path, host, date, digest);
+
+        let mut hasher = Sha256::new();
+        hasher.update(signing_string.as_bytes());
+        let digest_bytes = hasher.finalize();
+        let signature = self.private_key.sign(Pkcs1v15Sign::new::<Sha256>(), &digest_bytes)?;
+
+        Ok(format!(
+            r#"keyId="{}",algorithm="rsa-sha256",headers="(request-target) host date digest",signature="{}""#,
+            self.key_id,
+            BASE64.encode(signature)
+        ))
+    }
+}
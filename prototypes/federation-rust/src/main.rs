@@ -1,6 +1,7 @@
 use axum::{
-    extract::{Path, State},
-    http::{Method, StatusCode},
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, StatusCode},
     response::Json,
     routing::{get, post},
     Router, Server,
@@ -10,10 +11,26 @@ use std::sync::Arc;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{info, error};
 
+mod backend;
+mod cli;
+mod cluster;
+mod federation;
+mod graphql;
+mod httpsign;
+mod merkle;
 mod models;
+mod oplog;
+mod pipeline;
+mod query;
+mod reactive;
+mod schema;
 mod storage;
+#[cfg(test)]
+mod verify;
+mod wire;
 
-use storage::{FractalStorage, NodeStorage, ContributionStorage, FractalExpansionStorage, StorageMetadata};
+use cluster::{ClusterRoster, NodeRole};
+use storage::{FractalStorage, NodeStorage, ContributionStorage, FractalExpansionStorage, StorageMetadata, StoreOutcome};
 
 // ============================================================================
 // SERVER CONFIGURATION - Flexible settings like water adapting to containers
@@ -27,21 +44,93 @@ pub struct ServerConfig {
     pub enable_cors: bool,
     pub log_level: String,
     pub fractal_levels: Vec<u32>,
+    pub peers: Vec<String>,
+    /// Public hostname this server federates as, e.g. `https://codex.example.org`.
+    /// Used by `webfinger`, `get_actor`, and the federation endpoints instead
+    /// of baking in `http://localhost:8789`.
+    pub domain: String,
+    /// Whether this node accepts writes and persists locally (`ingest`, the
+    /// default and the only mode a single-node deployment needs) or serves
+    /// reads fanned out across a roster of ingest nodes (`query`).
+    pub role: NodeRole,
+    /// For an `ingest` node: the query node to register with on startup, e.g.
+    /// `http://query.internal:8789`. Ignored on a `query` node.
+    pub query_node: Option<String>,
+    /// Where `FractalStorage` durably persists node/contribution bytes.
+    /// Defaults to local files under `storage_path`; set via the `[backend]`
+    /// table in `config.toml` to select `Sqlite`/`S3` instead.
+    pub backend: backend::BackendConfig,
 }
 
 impl Default for ServerConfig {
     fn default() -> Self {
+        let storage_path = "./rust-fractal-storage".to_string();
         Self {
             host: "0.0.0.0".to_string(),
             port: 8789,
-            storage_path: "./rust-fractal-storage".to_string(),
+            backend: backend::BackendConfig::Local { base_path: storage_path.clone() },
+            storage_path,
             enable_cors: true,
             log_level: "info".to_string(),
             fractal_levels: vec![1, 2],
+            peers: vec![
+                "http://localhost:8787".to_string(),
+                "http://localhost:8788".to_string(),
+            ],
+            domain: "http://localhost:8789".to_string(),
+            role: NodeRole::Ingest,
+            query_node: None,
         }
     }
 }
 
+/// Shared router state. Handlers extract either piece individually via
+/// `State<Arc<FractalStorage>>` / `State<Arc<ServerConfig>>` (see the
+/// `FromRef` impls below), so adding config-dependent handlers doesn't
+/// require touching every existing one.
+#[derive(Clone)]
+pub struct AppState {
+    pub storage: Arc<FractalStorage>,
+    pub config: Arc<ServerConfig>,
+    pub graphql_schema: graphql::CodexSchema,
+    /// Roster of ingest nodes. Only populated (via `/cluster/register`) when
+    /// `config.role` is `Query`; empty and unused on an `ingest` node.
+    pub cluster: Arc<ClusterRoster>,
+    /// This node's own keypair, published via `/actor` and used to sign
+    /// outbound federation pushes. See `httpsign::SigningKey`.
+    pub signing_key: Arc<httpsign::SigningKey>,
+}
+
+impl axum::extract::FromRef<AppState> for Arc<FractalStorage> {
+    fn from_ref(state: &AppState) -> Self {
+        state.storage.clone()
+    }
+}
+
+impl axum::extract::FromRef<AppState> for Arc<ServerConfig> {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+impl axum::extract::FromRef<AppState> for graphql::CodexSchema {
+    fn from_ref(state: &AppState) -> Self {
+        state.graphql_schema.clone()
+    }
+}
+
+impl axum::extract::FromRef<AppState> for Arc<ClusterRoster> {
+    fn from_ref(state: &AppState) -> Self {
+        state.cluster.clone()
+    }
+}
+
+impl axum::extract::FromRef<AppState> for Arc<httpsign::SigningKey> {
+    fn from_ref(state: &AppState) -> Self {
+        state.signing_key.clone()
+    }
+}
+
 // ============================================================================
 // MAIN SERVER - Water-like flow and adaptation
 // ============================================================================
@@ -50,17 +139,33 @@ impl Default for ServerConfig {
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
     tracing_subscriber::fmt::init();
-    
+
+    let cli = <cli::Cli as clap::Parser>::parse();
+    let config = cli::load_config(&cli)?;
+
+    match cli.command.unwrap_or_default() {
+        cli::Command::Migrate => {
+            cli::run_migrate(&config).await?;
+            return Ok(());
+        }
+        cli::Command::Serve => {}
+    }
+
     info!("Living Codex Phase 6 - Rust Fractal Federation Server");
     info!("============================================================");
-    info!("Starting server on http://localhost:8789");
+    info!("Starting server on {}:{}", config.host, config.port);
+    info!("Federating as {}", config.domain);
     info!("Fractal levels: 1 (base nodes) + 2 (expanded contexts)");
     info!("Contexts: scientific, symbolic, water");
     info!("============================================================");
-    
-    // Initialize fractal storage with default config
+
+    // Initialize fractal storage at the configured path, using whichever
+    // backend `config.backend` selects (defaults to local files alongside
+    // the oplog; `config.toml`'s `[backend]` table can select Sqlite/S3).
     info!("Initializing fractal storage...");
-    let storage = match FractalStorage::new("./rust-fractal-storage").await {
+    let storage_config =
+        storage::StorageConfig { base_path: config.storage_path.clone(), backend: config.backend.clone(), ..Default::default() };
+    let storage = match FractalStorage::with_config(storage_config).await {
         Ok(storage) => {
             info!("Storage initialized successfully");
             Arc::new(storage)
@@ -70,15 +175,46 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             return Err(e.into());
         }
     };
-    
+
     info!("Storage ready, configuring server...");
-    
+
+    let signing_key_path = std::path::Path::new(&config.storage_path).join("signing_key.pem");
+    let signing_key = Arc::new(httpsign::SigningKey::load_or_generate(
+        &signing_key_path,
+        format!("{}/actor#main-key", config.domain),
+    )?);
+
+    let config = Arc::new(config);
+    let graphql_schema = graphql::build_schema(storage.clone(), config.clone());
+    let cluster = Arc::new(ClusterRoster::new());
+    let state = AppState { storage, config: config.clone(), graphql_schema, cluster: cluster.clone(), signing_key };
+
+    match config.role {
+        NodeRole::Query => {
+            info!("running as a query node; aggregating reads across registered ingest nodes");
+            cluster::spawn_health_checks(cluster, std::time::Duration::from_secs(30));
+        }
+        NodeRole::Ingest => {
+            if let Some(query_node) = config.query_node.clone() {
+                let self_url = config.domain.clone();
+                let fractal_levels = config.fractal_levels.clone();
+                tokio::spawn(async move {
+                    let capabilities = vec!["read".to_string(), "write".to_string()];
+                    if let Err(e) = cluster::register_with_query_node(&query_node, &self_url, capabilities, fractal_levels).await {
+                        error!("failed to register with query node {}: {}", query_node, e);
+                    }
+                });
+            }
+        }
+    }
+
     // Configure CORS
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods([Method::GET, Method::POST])
-        .allow_headers(Any);
-    
+    let cors = if config.enable_cors {
+        CorsLayer::new().allow_origin(Any).allow_methods([Method::GET, Method::POST]).allow_headers(Any)
+    } else {
+        CorsLayer::new()
+    };
+
     // Build router with trait-based endpoints
     let app = Router::new()
         .route("/", get(root))
@@ -89,6 +225,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/contributions/user/:user_id", get(get_user_contributions))
         .route("/inbox", post(post_to_inbox))
         .route("/outbox", get(get_outbox))
+        .route("/outbox/page/:page", get(get_outbox_page))
         .route("/fractal/expand/:node_id", get(get_fractal_expansion))
         .route("/fractal/nodes/:node_id", get(get_fractal_node))
         .route("/fractal/context/:context", get(get_fractal_context))
@@ -96,26 +233,32 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/.well-known/webfinger", get(webfinger))
         .route("/actor", get(get_actor))
         .route("/federation/peers", get(get_federation_peers))
-        .route("/federation/sync", get(federation_sync))
+        .route("/federation/sync", get(federation_sync).post(federation_sync_one))
+        .route("/cluster/register", post(cluster_register))
+        .route("/cluster/info", get(cluster_info))
+        .route("/graphql/sdl", get(get_graphql_sdl))
+        .route("/graphql", post(graphql_handler))
+        .route("/graphql/ws", get(graphql_ws_handler))
         .layer(cors)
-        .with_state(storage);
+        .with_state(state);
     
     info!("Router configured, starting server...");
-    
+
     // Start server with proper error handling
-    info!("Attempting to bind to 127.0.0.1:8789...");
-    let addr = match "127.0.0.1:8789".parse::<std::net::SocketAddr>() {
+    let bind_address = format!("{}:{}", config.host, config.port);
+    info!("Attempting to bind to {}...", bind_address);
+    let addr = match bind_address.parse::<std::net::SocketAddr>() {
         Ok(addr) => {
             info!("Successfully parsed address: {}", addr);
             addr
         },
         Err(e) => {
-            error!("Failed to parse address 127.0.0.1:8789: {}", e);
+            error!("Failed to parse address {}: {}", bind_address, e);
             return Err(format!("Address parse error: {}", e).into());
         }
     };
-    
-    info!("Server listening on http://localhost:8789");
+
+    info!("Server listening on {}", bind_address);
     info!("Starting axum server...");
     
     // Start the server using axum 0.6 compatible syntax
@@ -166,8 +309,15 @@ async fn root() -> Json<Value> {
 // STORAGE ENDPOINTS - Data management through traits
 // ============================================================================
 
-async fn get_storage_stats(State(storage): State<Arc<FractalStorage>>) -> Json<Value> {
-    let stats = storage.get_storage_stats().await;
+async fn get_storage_stats(
+    State(storage): State<Arc<FractalStorage>>,
+    State(config): State<Arc<ServerConfig>>,
+    State(cluster): State<Arc<ClusterRoster>>,
+) -> Json<Value> {
+    let stats = match config.role {
+        NodeRole::Query => cluster::aggregate_storage_stats(&cluster).await,
+        NodeRole::Ingest => storage.get_storage_stats().await,
+    };
     Json(json!(stats))
 }
 
@@ -182,20 +332,59 @@ async fn get_contribution(
     }
 }
 
+#[derive(serde::Deserialize)]
+struct PageQuery {
+    page: Option<usize>,
+}
+
+const PAGE_SIZE: usize = 20;
+
+/// Build a `next`/`prev`-linked page response from a (items, total) pair.
+fn paginated_response(base_path: &str, page: usize, items: Vec<impl serde::Serialize>, total: usize) -> Value {
+    let total_pages = total.div_ceil(PAGE_SIZE).max(1);
+    json!({
+        "items": items,
+        "page": page,
+        "page_size": PAGE_SIZE,
+        "total": total,
+        "total_pages": total_pages,
+        "next": if page + 1 < total_pages { Some(format!("{}?page={}", base_path, page + 1)) } else { None },
+        "prev": if page > 0 { Some(format!("{}?page={}", base_path, page - 1)) } else { None },
+    })
+}
+
 async fn get_node_contributions(
     State(storage): State<Arc<FractalStorage>>,
+    State(config): State<Arc<ServerConfig>>,
+    State(cluster): State<Arc<ClusterRoster>>,
     Path(node_id): Path<String>,
+    Query(query): Query<PageQuery>,
 ) -> Json<Value> {
-    let contributions = storage.get_node_contributions(&node_id).await;
-    Json(contributions)
+    if config.role == NodeRole::Query {
+        let path = move |_: &str| format!("/contributions/node/{}", node_id);
+        return Json(cluster::aggregate_contributions(&cluster, &storage, path).await);
+    }
+
+    let page = query.page.unwrap_or(0);
+    let (items, total) = storage.paginated_node_contributions(&node_id, page * PAGE_SIZE, PAGE_SIZE).await;
+    Json(paginated_response(&format!("/contributions/node/{}", node_id), page, items, total))
 }
 
 async fn get_user_contributions(
     State(storage): State<Arc<FractalStorage>>,
+    State(config): State<Arc<ServerConfig>>,
+    State(cluster): State<Arc<ClusterRoster>>,
     Path(user_id): Path<String>,
+    Query(query): Query<PageQuery>,
 ) -> Json<Value> {
-    let contributions = storage.get_user_contributions(&user_id).await;
-    Json(contributions)
+    if config.role == NodeRole::Query {
+        let path = move |_: &str| format!("/contributions/user/{}", user_id);
+        return Json(cluster::aggregate_contributions(&cluster, &storage, path).await);
+    }
+
+    let page = query.page.unwrap_or(0);
+    let (items, total) = storage.paginated_user_contributions(&user_id, page * PAGE_SIZE, PAGE_SIZE).await;
+    Json(paginated_response(&format!("/contributions/user/{}", user_id), page, items, total))
 }
 
 // ============================================================================
@@ -204,11 +393,28 @@ async fn get_user_contributions(
 
 async fn post_to_inbox(
     State(storage): State<Arc<FractalStorage>>,
-    Json(payload): Json<Value>,
+    State(config): State<Arc<ServerConfig>>,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> Result<Json<Value>, StatusCode> {
+    if config.role == NodeRole::Query {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // Every inbound activity must carry a verifiable HTTP Signature; the
+    // verified key owner becomes the trusted actor, not whatever the body claims.
+    let actor = match httpsign::verify_request(&headers, "POST", "/inbox", &body).await {
+        Ok(actor) => actor,
+        Err(e) => {
+            error!("inbox signature verification failed: {}", e);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+    };
+
+    let payload: Value = serde_json::from_slice(&body).map_err(|_| StatusCode::BAD_REQUEST)?;
+
     // Parse ActivityPub Create activity
     let activity_type = payload.get("type").and_then(|v| v.as_str()).unwrap_or("");
-    let actor = payload.get("actor").and_then(|v| v.as_str()).unwrap_or("anonymous");
     let object = payload.get("object");
     
     if activity_type != "Create" || object.is_none() {
@@ -220,6 +426,14 @@ async fn post_to_inbox(
     let content = obj.get("content").and_then(|v| v.as_str()).unwrap_or("");
     let resonance = obj.get("resonance").and_then(|v| v.as_f64()).unwrap_or(0.5);
     
+    // Dedup against local storage by content hash, same as `federation::pull_from_peer` -
+    // `push_to_peer` resends up to 50 recent contributions on every sync call, so without
+    // this an inbox that's synced with the same peer more than once accumulates duplicates.
+    let content_hash = storage.generate_content_hash(content);
+    if storage.get_contribution(&content_hash).await.is_some() {
+        return Ok(Json(json!({ "status": "accepted" })));
+    }
+
     // Create contribution with default context
     let contribution = models::Contribution::new(
         node_id.to_string(),
@@ -228,11 +442,16 @@ async fn post_to_inbox(
         resonance,
         None, // Will be determined by the node's contexts
     );
-    
+
     match storage.store_contribution(contribution).await {
-        Ok(result) => Ok(Json(json!({
+        Ok(StoreOutcome::Stored) => Ok(Json(json!({ "status": "accepted" }))),
+        Ok(StoreOutcome::Evicted { evicted_ids }) => Ok(Json(json!({
             "status": "accepted",
-            "result": result
+            "evicted": evicted_ids
+        }))),
+        Ok(StoreOutcome::RejectedBelowThreshold) => Ok(Json(json!({
+            "status": "rejected",
+            "reason": "below resonance threshold"
         }))),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
@@ -240,13 +459,47 @@ async fn post_to_inbox(
 
 async fn get_outbox(State(storage): State<Arc<FractalStorage>>) -> Json<Value> {
     let stats = storage.get_storage_stats().await;
-    
+    let total_pages = (stats.total_contributions as usize).div_ceil(PAGE_SIZE).max(1);
+
     Json(json!({
         "@context": "https://www.w3.org/ns/activitystreams",
         "id": "/outbox",
         "type": "OrderedCollection",
         "totalItems": stats.total_contributions,
-        "orderedItems": []
+        "first": "/outbox/page/0",
+        "last": format!("/outbox/page/{}", total_pages - 1)
+    }))
+}
+
+fn contribution_as_create_activity(c: &models::Contribution) -> Value {
+    json!({
+        "type": "Create",
+        "actor": c.user_id,
+        "object": {
+            "nodeId": c.node_id,
+            "content": c.content,
+            "resonance": c.resonance,
+            "timestamp": c.timestamp
+        }
+    })
+}
+
+async fn get_outbox_page(
+    State(storage): State<Arc<FractalStorage>>,
+    Path(page): Path<usize>,
+) -> Json<Value> {
+    let (items, total) = storage.paginated_contributions(page * PAGE_SIZE, PAGE_SIZE).await;
+    let total_pages = total.div_ceil(PAGE_SIZE).max(1);
+    let activities: Vec<Value> = items.iter().map(contribution_as_create_activity).collect();
+
+    Json(json!({
+        "@context": "https://www.w3.org/ns/activitystreams",
+        "id": format!("/outbox/page/{}", page),
+        "type": "OrderedCollectionPage",
+        "partOf": "/outbox",
+        "orderedItems": activities,
+        "next": if page + 1 < total_pages { Some(format!("/outbox/page/{}", page + 1)) } else { None },
+        "prev": if page > 0 { Some(format!("/outbox/page/{}", page - 1)) } else { None }
     }))
 }
 
@@ -289,20 +542,23 @@ async fn get_fractal_node(
 
 async fn get_fractal_context(
     State(storage): State<Arc<FractalStorage>>,
+    State(config): State<Arc<ServerConfig>>,
+    State(cluster): State<Arc<ClusterRoster>>,
     Path(context): Path<String>,
 ) -> Result<Json<Value>, StatusCode> {
     let valid_contexts = vec!["scientific", "symbolic", "water"];
     if !valid_contexts.contains(&context.as_str()) {
         return Err(StatusCode::BAD_REQUEST);
     }
-    
-    // Get all nodes for this context
-    let all_nodes = storage.get_all_nodes().await;
-    let context_nodes = storage::group_nodes_by_context(&all_nodes);
-    
-    let context_key = context.to_string();
-    let nodes = context_nodes.get(&context_key).cloned().unwrap_or_default();
-    
+
+    let nodes = if config.role == NodeRole::Query {
+        cluster::aggregate_fractal_context(&cluster, &context).await
+    } else {
+        let all_nodes = storage.get_all_nodes().await;
+        let context_nodes = storage::group_nodes_by_context(&all_nodes);
+        context_nodes.get(&context).cloned().unwrap_or_default()
+    };
+
     Ok(Json(json!({
         "context": context,
         "nodes": nodes,
@@ -345,39 +601,50 @@ async fn get_fractal_levels(State(storage): State<Arc<FractalStorage>>) -> Json<
 // FEDERATION ENDPOINTS - Inter-server communication
 // ============================================================================
 
-async fn webfinger() -> Json<Value> {
+async fn webfinger(State(config): State<Arc<ServerConfig>>) -> Json<Value> {
+    let domain = &config.domain;
     Json(json!({
-        "subject": "acct:fractal@localhost",
+        "subject": format!("acct:fractal@{}", host_from_domain(domain)),
         "links": [
             {
                 "rel": "self",
                 "type": "application/activity+json",
-                "href": "http://localhost:8789/actor"
+                "href": format!("{}/actor", domain)
             },
             {
                 "rel": "http://www.w3.org/ns/activitystreams#inbox",
                 "type": "application/activity+json",
-                "href": "http://localhost:8789/inbox"
+                "href": format!("{}/inbox", domain)
             },
             {
                 "rel": "http://www.w3.org/ns/activitystreams#outbox",
                 "type": "application/activity+json",
-                "href": "http://localhost:8789/outbox"
+                "href": format!("{}/outbox", domain)
             }
         ]
     }))
 }
 
-async fn get_actor() -> Json<Value> {
-    Json(json!({
+async fn get_actor(
+    State(config): State<Arc<ServerConfig>>,
+    State(signing_key): State<Arc<httpsign::SigningKey>>,
+) -> Result<Json<Value>, StatusCode> {
+    let domain = &config.domain;
+    let public_key_pem = signing_key.public_key_pem().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(json!({
         "@context": "https://www.w3.org/ns/activitystreams",
-        "id": "http://localhost:8789/actor",
+        "id": format!("{}/actor", domain),
         "type": "Person",
         "name": "Living Codex Rust Fractal Federation",
         "summary": "Phase 6 federation server with trait-based architecture",
-        "inbox": "http://localhost:8789/inbox",
-        "outbox": "http://localhost:8789/outbox",
+        "inbox": format!("{}/inbox", domain),
+        "outbox": format!("{}/outbox", domain),
         "preferredUsername": "fractal",
+        "publicKey": {
+            "id": signing_key.key_id,
+            "owner": format!("{}/actor", domain),
+            "publicKeyPem": public_key_pem
+        },
         "fractal_capabilities": {
             "levels": [1, 2],
             "contexts": ["scientific", "symbolic", "water"],
@@ -385,44 +652,111 @@ async fn get_actor() -> Json<Value> {
             "architecture": "trait-based",
             "flexibility": "water-like"
         }
+    })))
+}
+
+/// Strip the scheme from a domain like `https://codex.example.org` to get
+/// the bare host used in a webfinger `acct:` subject.
+fn host_from_domain(domain: &str) -> &str {
+    domain.split("://").nth(1).unwrap_or(domain)
+}
+
+async fn get_federation_peers(State(config): State<Arc<ServerConfig>>) -> Json<Value> {
+    Json(json!({
+        "peers": config.peers.iter().map(|url| json!({ "url": url, "status": "configured" })).collect::<Vec<_>>()
     }))
 }
 
-async fn get_federation_peers() -> Json<Value> {
+/// Sync with every configured peer: pull new contributions from each peer's
+/// outbox (deduplicated by `content_hash`) and push our own recent
+/// contributions to each peer's inbox.
+async fn federation_sync(
+    State(storage): State<Arc<FractalStorage>>,
+    State(config): State<Arc<ServerConfig>>,
+    State(signing_key): State<Arc<httpsign::SigningKey>>,
+) -> Json<Value> {
+    let results = federation::sync_all_peers(&storage, &config.peers, &config.domain, &signing_key).await;
+
     Json(json!({
-        "peers": [
-            {
-                "id": "nodejs@localhost:8787",
-                "url": "http://localhost:8787",
-                "capabilities": ["phase4", "federation", "storage"],
-                "status": "active"
-            },
-            {
-                "id": "python@localhost:8788",
-                "url": "http://localhost:8788",
-                "capabilities": ["phase4", "phase5", "federation", "fractal_expansion"],
-                "status": "active"
-            },
-            {
-                "id": "rust@localhost:8789",
-                "url": "http://localhost:8789",
-                "capabilities": ["phase4", "phase5", "phase6", "federation", "fractal_expansion", "trait_architecture", "water_like"],
-                "status": "active"
-            }
-        ]
+        "synced": true,
+        "timestamp": chrono::Utc::now(),
+        "peers_synced": results.len(),
+        "results": results
     }))
 }
 
-async fn federation_sync() -> Json<Value> {
+#[derive(serde::Deserialize)]
+struct SyncOnePeerRequest {
+    peer: String,
+}
+
+/// Trigger sync on demand for a single peer rather than every configured one.
+async fn federation_sync_one(
+    State(storage): State<Arc<FractalStorage>>,
+    State(config): State<Arc<ServerConfig>>,
+    State(signing_key): State<Arc<httpsign::SigningKey>>,
+    Json(payload): Json<SyncOnePeerRequest>,
+) -> Json<Value> {
+    let result = federation::sync_peer(&storage, &payload.peer, &config.domain, &signing_key).await;
+
     Json(json!({
         "synced": true,
         "timestamp": chrono::Utc::now(),
-        "fractal_levels_synced": [1, 2],
-        "architecture": "trait-based",
-        "flexibility": "water-like"
+        "result": result
     }))
 }
 
+// ============================================================================
+// CLUSTER ENDPOINTS - Ingest/query roster for horizontal scaling
+// ============================================================================
+
+/// An ingest node calls this on startup to join a query node's roster.
+/// Meaningless on an ingest node itself, since it has no roster of its own.
+async fn cluster_register(
+    State(config): State<Arc<ServerConfig>>,
+    State(cluster): State<Arc<ClusterRoster>>,
+    Json(req): Json<cluster::RegisterRequest>,
+) -> Result<Json<Value>, StatusCode> {
+    if config.role != NodeRole::Query {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    info!("cluster: registering ingest node {}", req.url);
+    cluster.register(req).await;
+    Ok(Json(json!({ "status": "registered" })))
+}
+
+async fn cluster_info(State(cluster): State<Arc<ClusterRoster>>) -> Json<Value> {
+    Json(json!({ "ingest_nodes": cluster.snapshot().await }))
+}
+
+// ============================================================================
+// GRAPHQL ENDPOINTS - Typed schema export for client codegen
+// ============================================================================
+
+/// SDL export for client codegen, generated straight from the live
+/// `async-graphql` schema so it can never drift from what `/graphql` actually
+/// serves.
+async fn get_graphql_sdl(State(schema): State<graphql::CodexSchema>) -> String {
+    schema.sdl()
+}
+
+async fn graphql_handler(
+    State(schema): State<graphql::CodexSchema>,
+    req: async_graphql_axum::GraphQLRequest,
+) -> async_graphql_axum::GraphQLResponse {
+    schema.execute(req.into_inner()).await.into()
+}
+
+async fn graphql_ws_handler(
+    State(schema): State<graphql::CodexSchema>,
+    protocol: async_graphql_axum::GraphQLProtocol,
+    upgrade: axum::extract::WebSocketUpgrade,
+) -> axum::response::Response {
+    upgrade
+        .on_upgrade(move |socket| async_graphql_axum::GraphQLWebSocket::new(socket, schema, protocol).serve())
+}
+
 async fn health_check() -> Json<Value> {
     Json(json!({
         "status": "ok",
@@ -0,0 +1,192 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+// ============================================================================
+// APPEND-ONLY MERKLE TREE - Tamper-evident commitment over accepted content
+// ============================================================================
+//
+// Leaves are pushed in acceptance order and never removed or reordered, so
+// `append` only has to recompute the right-most path from the new leaf to
+// the root instead of rebuilding every level from scratch. A level with an
+// odd number of entries promotes its last (unpaired) entry unchanged to the
+// next level, rather than hashing it against a placeholder.
+
+pub type Hash = [u8; 32];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+#[derive(Debug, Default)]
+pub struct AppendMerkleTree {
+    /// `levels[0]` is the leaves; `levels[i]` is built from `levels[i-1]`.
+    levels: Vec<Vec<Hash>>,
+    /// Leaf hash -> index in `levels[0]`, for O(1) inclusion-proof lookups.
+    leaf_indices: HashMap<Hash, usize>,
+}
+
+impl AppendMerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a new leaf, updating only the right-most path up to the root.
+    pub fn append(&mut self, leaf: Hash) {
+        if self.levels.is_empty() {
+            self.levels.push(Vec::new());
+        }
+
+        self.leaf_indices.entry(leaf).or_insert(self.levels[0].len());
+        self.levels[0].push(leaf);
+
+        let mut level = 0;
+        while self.levels[level].len() > 1 {
+            let len = self.levels[level].len();
+            let parent_index = (len - 1) / 2;
+            let left = self.levels[level][2 * parent_index];
+            let parent_value = if 2 * parent_index + 1 < len {
+                hash_pair(&left, &self.levels[level][2 * parent_index + 1])
+            } else {
+                left
+            };
+
+            if level + 1 == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+            if parent_index < self.levels[level + 1].len() {
+                self.levels[level + 1][parent_index] = parent_value;
+            } else {
+                self.levels[level + 1].push(parent_value);
+            }
+
+            level += 1;
+        }
+    }
+
+    /// The current 32-byte root commitment, or `None` if no leaves have
+    /// been appended yet.
+    pub fn root(&self) -> Option<Hash> {
+        self.levels.last().and_then(|top| top.first()).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels.first().map(|leaves| leaves.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sibling hashes and left/right positions from `leaf` up to the root,
+    /// in order. A level where `leaf`'s ancestor was promoted unchanged
+    /// (no sibling yet) contributes no entry, matching `append`'s behavior.
+    pub fn get_inclusion_proof(&self, leaf: &Hash) -> Option<Vec<(Hash, Side)>> {
+        let mut index = *self.leaf_indices.get(leaf)?;
+        let mut proof = Vec::new();
+
+        for level in &self.levels {
+            if level.len() <= 1 {
+                break;
+            }
+            let sibling_index = index ^ 1;
+            if sibling_index < level.len() {
+                let side = if index % 2 == 0 { Side::Right } else { Side::Left };
+                proof.push((level[sibling_index], side));
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+}
+
+/// Replay a leaf-to-root inclusion proof and check it reproduces `root`.
+/// Pure function: no dependency on any particular tree instance.
+pub fn verify_inclusion(leaf: Hash, proof: &[(Hash, Side)], root: Hash) -> bool {
+    let mut current = leaf;
+    for (sibling, side) in proof {
+        current = match side {
+            Side::Left => hash_pair(sibling, &current),
+            Side::Right => hash_pair(&current, sibling),
+        };
+    }
+    current == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(content: &str) -> Hash {
+        let mut hasher = Sha256::new();
+        hasher.update(content.as_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Every leaf's inclusion proof must verify against the tree's root,
+    /// across both an even and an odd leaf count so the "promote the
+    /// unpaired last entry" path gets exercised too.
+    fn assert_every_leaf_proves_inclusion(count: usize) {
+        let mut tree = AppendMerkleTree::new();
+        let leaves: Vec<Hash> = (0..count).map(|i| leaf(&format!("leaf-{}", i))).collect();
+        for l in &leaves {
+            tree.append(*l);
+        }
+        let root = tree.root().expect("non-empty tree has a root");
+
+        for l in &leaves {
+            let proof = tree.get_inclusion_proof(l).expect("appended leaf has a proof");
+            assert!(verify_inclusion(*l, &proof, root), "leaf {:?} failed to verify", l);
+        }
+    }
+
+    #[test]
+    fn inclusion_proof_verifies_for_every_leaf_with_an_even_leaf_count() {
+        assert_every_leaf_proves_inclusion(4);
+    }
+
+    #[test]
+    fn inclusion_proof_verifies_for_every_leaf_with_an_odd_leaf_count() {
+        assert_every_leaf_proves_inclusion(5);
+    }
+
+    #[test]
+    fn inclusion_proof_verifies_for_a_single_leaf() {
+        assert_every_leaf_proves_inclusion(1);
+    }
+
+    #[test]
+    fn get_inclusion_proof_is_none_for_a_leaf_never_appended() {
+        let mut tree = AppendMerkleTree::new();
+        tree.append(leaf("present"));
+        assert!(tree.get_inclusion_proof(&leaf("absent")).is_none());
+    }
+
+    #[test]
+    fn verify_inclusion_rejects_a_proof_against_the_wrong_root() {
+        let mut tree = AppendMerkleTree::new();
+        for i in 0..3 {
+            tree.append(leaf(&format!("leaf-{}", i)));
+        }
+        let target = leaf("leaf-0");
+        let proof = tree.get_inclusion_proof(&target).unwrap();
+        let wrong_root = leaf("not-the-real-root");
+        assert!(!verify_inclusion(target, &proof, wrong_root));
+    }
+
+    #[test]
+    fn empty_tree_has_no_root() {
+        let tree = AppendMerkleTree::new();
+        assert!(tree.root().is_none());
+        assert!(tree.is_empty());
+    }
+}
@@ -98,6 +98,11 @@ pub struct FractalNode {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Layout version this record was written in. Missing on records
+    /// persisted before this field existed; `crate::schema` migrates those
+    /// forward to `crate::schema::CURRENT_SCHEMA_VERSION` on load.
+    #[serde(default = "crate::schema::legacy_schema_version")]
+    pub schema_version: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,6 +115,9 @@ pub struct Contribution {
     pub timestamp: DateTime<Utc>,
     pub fractal_context: Option<ContextType>,
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Layout version this record was written in. See `FractalNode::schema_version`.
+    #[serde(default = "crate::schema::legacy_schema_version")]
+    pub schema_version: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +131,17 @@ pub struct StorageStats {
     pub total_size: u64,
     pub last_updated: DateTime<Utc>,
     pub fractal_expansion: FractalExpansionStats,
+    /// Hex-encoded root of the append-only Merkle tree over every accepted
+    /// contribution's content hash. `None` until the first contribution is
+    /// stored; lets a reloaded store confirm its contribution set matches
+    /// what was last committed.
+    pub contribution_root: Option<String>,
+    /// Contributions rejected by `store_contribution` for having `resonance`
+    /// below `StorageConfig::resonance_threshold`.
+    pub contributions_rejected: u64,
+    /// Nodes and contributions removed by `StorageConfig::eviction` to stay
+    /// within `max_nodes`/`max_contributions`.
+    pub evictions: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -159,6 +178,7 @@ impl FractalNode {
             created_at: now,
             updated_at: now,
             metadata: HashMap::new(),
+            schema_version: crate::schema::CURRENT_SCHEMA_VERSION,
         }
     }
 
@@ -204,6 +224,7 @@ impl Contribution {
             timestamp: Utc::now(),
             fractal_context,
             metadata: HashMap::new(),
+            schema_version: crate::schema::CURRENT_SCHEMA_VERSION,
         }
     }
 
@@ -257,6 +278,27 @@ impl Transformable for FractalNode {
     }
 }
 
+/// Frequency distance within which two nodes can resonate. `verify.rs`
+/// property-tests that this exactly coincides with `resonance_strength`
+/// dropping to 0, so any caller deriving its own notion of "resonance" (e.g.
+/// `storage::sync_resonance_signal`'s reactive edges) should go through
+/// `resonance_edge_strength` below rather than re-deriving the cutoff.
+pub const RESONANCE_FREQUENCY_CUTOFF: f64 = 10.0;
+
+/// The strength two resonance values (not frequencies) resonate at: 0 once
+/// their frequency distance reaches `RESONANCE_FREQUENCY_CUTOFF`, otherwise an
+/// inverse relationship to how close they are. Shared by
+/// `Resonant::resonance_strength` below and `storage::sync_resonance_signal`,
+/// so both compute "resonance" the same way.
+pub fn resonance_edge_strength(a_resonance: f64, b_resonance: f64) -> f64 {
+    let freq_diff = (a_resonance * 100.0 - b_resonance * 100.0).abs();
+    if freq_diff < RESONANCE_FREQUENCY_CUTOFF {
+        1.0 / (1.0 + freq_diff / RESONANCE_FREQUENCY_CUTOFF)
+    } else {
+        0.0
+    }
+}
+
 impl Resonant for FractalNode {
     fn resonance_frequency(&self) -> f64 {
         self.resonance * 100.0 // Convert to frequency range
@@ -264,16 +306,11 @@ impl Resonant for FractalNode {
 
     fn can_resonate_with(&self, other: &dyn Resonant) -> bool {
         let freq_diff = (self.resonance_frequency() - other.resonance_frequency()).abs();
-        freq_diff < 10.0 // Within resonance range
+        freq_diff < RESONANCE_FREQUENCY_CUTOFF // Within resonance range
     }
 
     fn resonance_strength(&self, other: &dyn Resonant) -> f64 {
-        if self.can_resonate_with(other) {
-            let freq_diff = (self.resonance_frequency() - other.resonance_frequency()).abs();
-            1.0 / (1.0 + freq_diff / 10.0) // Inverse relationship
-        } else {
-            0.0
-        }
+        resonance_edge_strength(self.resonance, other.resonance_frequency() / 100.0)
     }
 }
 
@@ -301,6 +338,9 @@ impl Default for StorageStats {
                 ],
                 level_breakdown: HashMap::new(),
             },
+            contribution_root: None,
+            contributions_rejected: 0,
+            evictions: 0,
         }
     }
 }
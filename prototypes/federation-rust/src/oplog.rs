@@ -0,0 +1,270 @@
+use crate::models::{Contribution, ContextType, FractalNode};
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::warn;
+
+// ============================================================================
+// OPERATION LOG - Event-sourced persistence for crash-safe recovery
+// ============================================================================
+//
+// Every mutating `FractalStorage` call appends a `LoggedOperation` to an
+// on-disk log before returning. Every `checkpoint_interval` operations, the
+// full in-memory state is snapshotted to a `Checkpoint` file. On startup,
+// `recover()` loads the most recent valid checkpoint and replays only the
+// operations logged after it, so a crash between writes loses nothing that
+// was appended - the log, not the in-memory maps, is the source of truth.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Operation {
+    StoreNode(FractalNode),
+    DeleteNode(String),
+    StoreContribution(Contribution),
+    DeleteContribution(String),
+    AddNodeToContext { context: ContextType, node_id: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedOperation {
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub op: Operation,
+}
+
+/// A full snapshot of storage state at `seq`. `node_contexts` is a `Vec` of
+/// pairs rather than a `HashMap<ContextType, _>` because `ContextType` isn't
+/// a string, and `serde_json` can only serialize map keys that are.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Checkpoint {
+    pub seq: u64,
+    pub nodes: HashMap<String, FractalNode>,
+    pub contributions: HashMap<String, Contribution>,
+    pub node_contexts: Vec<(ContextType, Vec<String>)>,
+    /// Content hash of every contribution ever accepted, in append order -
+    /// unlike `contributions`, entries are never removed when one is evicted,
+    /// since the Merkle tree built over them is append-only too. See
+    /// `FractalStorage::rebuild_contribution_tree`.
+    pub contribution_tree_leaf_hashes: Vec<String>,
+}
+
+/// Everything needed to rebuild in-memory state: the last checkpoint (if
+/// any) plus the operations logged strictly after it, in order.
+pub struct Recovered {
+    pub checkpoint: Option<Checkpoint>,
+    pub ops: Vec<LoggedOperation>,
+    pub next_seq: u64,
+}
+
+pub struct OpLog {
+    log_path: PathBuf,
+    checkpoint_path: PathBuf,
+    checkpoint_interval: u64,
+    seq: AtomicU64,
+    ops_since_checkpoint: AtomicU64,
+}
+
+impl OpLog {
+    pub fn open(base_path: &str, checkpoint_interval: usize) -> Result<Self> {
+        let dir = Path::new(base_path).join("oplog");
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            log_path: dir.join("log.jsonl"),
+            checkpoint_path: dir.join("checkpoint.json"),
+            checkpoint_interval: checkpoint_interval.max(1) as u64,
+            seq: AtomicU64::new(0),
+            ops_since_checkpoint: AtomicU64::new(0),
+        })
+    }
+
+    /// Load the last checkpoint (if any and valid) and every operation
+    /// logged after it. A partially written trailing log line (e.g. the
+    /// process died mid-`write`) is detected and discarded rather than
+    /// aborting recovery.
+    pub fn recover(&self) -> Result<Recovered> {
+        let checkpoint = self.read_checkpoint();
+        let checkpoint_seq = checkpoint.as_ref().map(|c| c.seq).unwrap_or(0);
+
+        let mut ops = Vec::new();
+        let mut max_seq = checkpoint_seq;
+
+        if self.log_path.exists() {
+            let file = std::fs::File::open(&self.log_path).context("opening oplog")?;
+            let reader = std::io::BufReader::new(file);
+            let lines: Vec<_> = reader.lines().collect::<std::io::Result<_>>().context("reading oplog")?;
+            let line_count = lines.len();
+
+            for (i, line) in lines.into_iter().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<LoggedOperation>(&line) {
+                    Ok(logged) => {
+                        if logged.seq > checkpoint_seq {
+                            max_seq = max_seq.max(logged.seq);
+                            ops.push(logged);
+                        }
+                    }
+                    Err(e) => {
+                        if i + 1 == line_count {
+                            warn!("discarding partially written trailing oplog entry: {}", e);
+                        } else {
+                            warn!("discarding corrupt oplog entry at line {}: {}", i + 1, e);
+                        }
+                    }
+                }
+            }
+        }
+
+        ops.sort_by_key(|logged| logged.seq);
+        self.seq.store(max_seq, Ordering::SeqCst);
+        self.ops_since_checkpoint.store(ops.len() as u64, Ordering::SeqCst);
+
+        Ok(Recovered { checkpoint, ops, next_seq: max_seq + 1 })
+    }
+
+    fn read_checkpoint(&self) -> Option<Checkpoint> {
+        let text = std::fs::read_to_string(&self.checkpoint_path).ok()?;
+        match serde_json::from_str(&text) {
+            Ok(checkpoint) => Some(checkpoint),
+            Err(e) => {
+                warn!("discarding unreadable checkpoint file: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Append one operation to the log, assigning it the next sequence
+    /// number. Returns the checkpoint, if any, that should now be written by
+    /// the caller (which owns the in-memory state being snapshotted).
+    pub async fn append(&self, op: Operation) -> Result<ShouldCheckpoint> {
+        let seq = self.seq.fetch_add(1, Ordering::SeqCst) + 1;
+        let logged = LoggedOperation { seq, timestamp: Utc::now(), op };
+        let line = serde_json::to_string(&logged)?;
+        let path = self.log_path.clone();
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+            Ok(())
+        })
+        .await
+        .context("oplog append task panicked")??;
+
+        let since = self.ops_since_checkpoint.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(if since >= self.checkpoint_interval { ShouldCheckpoint::Yes(seq) } else { ShouldCheckpoint::No })
+    }
+
+    /// Write a full checkpoint at `seq` and reset the since-checkpoint
+    /// counter. The log itself is left intact; `recover` simply skips any
+    /// entry at or before the checkpoint's `seq` on the next load.
+    pub async fn write_checkpoint(&self, checkpoint: Checkpoint) -> Result<()> {
+        let path = self.checkpoint_path.clone();
+        let tmp_path = path.with_extension("json.tmp");
+        let bytes = serde_json::to_vec(&checkpoint)?;
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            std::fs::write(&tmp_path, bytes)?;
+            std::fs::rename(&tmp_path, &path)?;
+            Ok(())
+        })
+        .await
+        .context("checkpoint write task panicked")??;
+
+        self.ops_since_checkpoint.store(0, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+pub enum ShouldCheckpoint {
+    Yes(u64),
+    No,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    /// A fresh scratch directory per test, so parallel test runs don't
+    /// collide on the same `oplog/` files.
+    fn temp_base_path() -> PathBuf {
+        let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("codex-oplog-test-{}-{}", std::process::id(), n));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn logged(seq: u64) -> LoggedOperation {
+        LoggedOperation { seq, timestamp: Utc::now(), op: Operation::DeleteNode(format!("node-{}", seq)) }
+    }
+
+    fn write_log_lines(oplog: &OpLog, lines: &[String]) {
+        std::fs::write(&oplog.log_path, lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn recover_replays_only_operations_after_the_checkpoint() {
+        let base_path = temp_base_path();
+        let oplog = OpLog::open(base_path.to_str().unwrap(), 100).unwrap();
+
+        let checkpoint = Checkpoint { seq: 5, ..Checkpoint::default() };
+        std::fs::write(&oplog.checkpoint_path, serde_json::to_vec(&checkpoint).unwrap()).unwrap();
+
+        let lines: Vec<String> = [3, 4, 5, 6, 7].iter().map(|&seq| serde_json::to_string(&logged(seq)).unwrap()).collect();
+        write_log_lines(&oplog, &lines);
+
+        let recovered = oplog.recover().unwrap();
+        assert_eq!(recovered.checkpoint.unwrap().seq, 5);
+        assert_eq!(recovered.ops.iter().map(|o| o.seq).collect::<Vec<_>>(), vec![6, 7]);
+        assert_eq!(recovered.next_seq, 8);
+    }
+
+    #[test]
+    fn recover_discards_a_partially_written_trailing_line() {
+        let base_path = temp_base_path();
+        let oplog = OpLog::open(base_path.to_str().unwrap(), 100).unwrap();
+
+        let mut lines: Vec<String> = [1, 2].iter().map(|&seq| serde_json::to_string(&logged(seq)).unwrap()).collect();
+        lines.push(r#"{"seq": 3, "timestamp": "2024-01-01T00:00:00Z", "op": {"DeleteNode"#.to_string());
+        write_log_lines(&oplog, &lines);
+
+        let recovered = oplog.recover().unwrap();
+        assert!(recovered.checkpoint.is_none());
+        assert_eq!(recovered.ops.iter().map(|o| o.seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(recovered.next_seq, 3);
+    }
+
+    #[test]
+    fn recover_discards_a_corrupt_line_that_is_not_the_last_but_keeps_later_entries() {
+        let base_path = temp_base_path();
+        let oplog = OpLog::open(base_path.to_str().unwrap(), 100).unwrap();
+
+        let lines = vec![
+            serde_json::to_string(&logged(1)).unwrap(),
+            "not valid json at all".to_string(),
+            serde_json::to_string(&logged(2)).unwrap(),
+        ];
+        write_log_lines(&oplog, &lines);
+
+        let recovered = oplog.recover().unwrap();
+        assert_eq!(recovered.ops.iter().map(|o| o.seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(recovered.next_seq, 3);
+    }
+
+    #[test]
+    fn recover_with_no_log_or_checkpoint_starts_fresh() {
+        let base_path = temp_base_path();
+        let oplog = OpLog::open(base_path.to_str().unwrap(), 100).unwrap();
+
+        let recovered = oplog.recover().unwrap();
+        assert!(recovered.checkpoint.is_none());
+        assert!(recovered.ops.is_empty());
+        assert_eq!(recovered.next_seq, 1);
+    }
+}
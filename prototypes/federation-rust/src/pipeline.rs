@@ -0,0 +1,299 @@
+use crate::models::{Contribution, FractalNode};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+// ============================================================================
+// SOURCE/SINK SUBSYSTEM - Streaming contributions in and node updates out
+// ============================================================================
+//
+// Modeled on Materialize's source/sink split: a `Source` produces an async
+// stream of `Contribution`s, a `Sink` consumes filtered `FractalNode` change
+// events, and a pipeline wires `source -> filter -> sink` declaratively.
+
+/// A resumable position within a source. `run_pipeline` persists it via
+/// `FractalStorage::save_cursor`; reopening a `FileTailSource` with the value
+/// from `FractalStorage::load_cursor` picks up ingestion where it left off.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Cursor {
+    pub last_timestamp: Option<DateTime<Utc>>,
+    pub last_id: Option<String>,
+}
+
+impl Cursor {
+    pub fn advance(&mut self, contribution: &Contribution) {
+        self.last_timestamp = Some(contribution.timestamp);
+        self.last_id = Some(contribution.id.clone());
+    }
+}
+
+/// Produces a stream of `Contribution`s from some external feed.
+pub trait Source: Send {
+    fn name(&self) -> &str;
+    async fn next(&mut self) -> Result<Option<Contribution>>;
+    fn cursor(&self) -> Cursor;
+}
+
+/// Consumes `FractalNode` change events, e.g. writing them downstream.
+pub trait Sink: Send {
+    fn name(&self) -> &str;
+    async fn send(&mut self, node: &FractalNode) -> Result<()>;
+}
+
+// ============================================================================
+// SOURCES
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub struct FileTailSourceConfig {
+    pub path: PathBuf,
+}
+
+/// Tails a newline-delimited JSON file of `Contribution`s, resuming just past
+/// the line whose id is `cursor.last_id` (the last one successfully read).
+pub struct FileTailSource {
+    config: FileTailSourceConfig,
+    reader: Option<BufReader<tokio::fs::File>>,
+    cursor: Cursor,
+}
+
+impl FileTailSource {
+    /// Open `config.path` and fast-forward past every line up to and
+    /// including the one matching `cursor.last_id`, so `next()` picks up
+    /// right after it. Pass `Cursor::default()` to start from the beginning.
+    pub async fn new(config: FileTailSourceConfig, cursor: Cursor) -> Result<Self> {
+        let file = tokio::fs::File::open(&config.path).await?;
+        let mut reader = BufReader::new(file);
+
+        if let Some(last_id) = cursor.last_id.clone() {
+            let mut line = String::new();
+            loop {
+                line.clear();
+                let bytes_read = reader.read_line(&mut line).await?;
+                if bytes_read == 0 {
+                    warn!("file_tail: cursor last_id {} not found in {}; starting from the beginning", last_id, config.path.display());
+                    let file = tokio::fs::File::open(&config.path).await?;
+                    reader = BufReader::new(file);
+                    break;
+                }
+                let seen: Contribution = serde_json::from_str(line.trim())?;
+                if seen.id == last_id {
+                    break;
+                }
+            }
+        }
+
+        Ok(Self { config, reader: Some(reader), cursor })
+    }
+}
+
+impl Source for FileTailSource {
+    fn name(&self) -> &str {
+        "file_tail"
+    }
+
+    async fn next(&mut self) -> Result<Option<Contribution>> {
+        let Some(reader) = self.reader.as_mut() else { return Ok(None) };
+        let mut line = String::new();
+        let bytes_read = reader.read_line(&mut line).await?;
+        if bytes_read == 0 {
+            return Ok(None);
+        }
+        let contribution: Contribution = serde_json::from_str(line.trim())?;
+        self.cursor.advance(&contribution);
+        Ok(Some(contribution))
+    }
+
+    fn cursor(&self) -> Cursor {
+        self.cursor.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpWebhookSourceConfig {
+    pub listen_path: String,
+}
+
+/// Yields, in order, contributions pushed into the shared queue that `new`
+/// returns alongside it. `listen_path` only names the source; no route is
+/// mounted for it in this crate.
+pub struct HttpWebhookSource {
+    config: HttpWebhookSourceConfig,
+    inbound: Arc<Mutex<VecDeque<Contribution>>>,
+    cursor: Cursor,
+}
+
+impl HttpWebhookSource {
+    pub fn new(config: HttpWebhookSourceConfig) -> (Self, Arc<Mutex<VecDeque<Contribution>>>) {
+        let inbound = Arc::new(Mutex::new(VecDeque::new()));
+        (Self { config, inbound: inbound.clone(), cursor: Cursor::default() }, inbound)
+    }
+}
+
+impl Source for HttpWebhookSource {
+    fn name(&self) -> &str {
+        &self.config.listen_path
+    }
+
+    async fn next(&mut self) -> Result<Option<Contribution>> {
+        let mut queue = self.inbound.lock().await;
+        let next = queue.pop_front();
+        drop(queue);
+        if let Some(contribution) = &next {
+            self.cursor.advance(contribution);
+        }
+        Ok(next)
+    }
+
+    fn cursor(&self) -> Cursor {
+        self.cursor.clone()
+    }
+}
+
+/// An in-memory source for tests and local pipelines, replaying a fixed list.
+pub struct InMemoryTestSource {
+    pending: VecDeque<Contribution>,
+    cursor: Cursor,
+}
+
+impl InMemoryTestSource {
+    pub fn new(contributions: Vec<Contribution>) -> Self {
+        Self { pending: contributions.into(), cursor: Cursor::default() }
+    }
+}
+
+impl Source for InMemoryTestSource {
+    fn name(&self) -> &str {
+        "in_memory_test"
+    }
+
+    async fn next(&mut self) -> Result<Option<Contribution>> {
+        let next = self.pending.pop_front();
+        if let Some(contribution) = &next {
+            self.cursor.advance(contribution);
+        }
+        Ok(next)
+    }
+
+    fn cursor(&self) -> Cursor {
+        self.cursor.clone()
+    }
+}
+
+// ============================================================================
+// SINKS
+// ============================================================================
+
+/// Appends each node as a JSON line to a file.
+pub struct JsonLinesSink {
+    path: PathBuf,
+}
+
+impl JsonLinesSink {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl Sink for JsonLinesSink {
+    fn name(&self) -> &str {
+        "json_lines"
+    }
+
+    async fn send(&mut self, node: &FractalNode) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        let mut line = serde_json::to_string(node)?;
+        line.push('\n');
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// Wraps another sink, forwarding only nodes at or above `resonance_level`.
+pub struct ResonanceFilterSink<S: Sink> {
+    inner: S,
+    resonance_level: f64,
+}
+
+impl<S: Sink> ResonanceFilterSink<S> {
+    pub fn new(inner: S, resonance_level: f64) -> Self {
+        Self { inner, resonance_level }
+    }
+}
+
+impl<S: Sink> Sink for ResonanceFilterSink<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn send(&mut self, node: &FractalNode) -> Result<()> {
+        if node.resonance >= self.resonance_level {
+            self.inner.send(node).await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// ============================================================================
+// PIPELINE RUNNER - wires `source -> storage -> sink` declaratively
+// ============================================================================
+
+/// Drains `source`, storing each contribution through `storage` and forwarding
+/// the affected node through the sink (wrap in `ResonanceFilterSink` for a
+/// `source -> resonance filter -> sink` pipeline). Saves the source's final
+/// cursor via `storage.save_cursor` and returns it, so a `FileTailSource`
+/// reopened with it (via `storage.load_cursor`) resumes after a restart.
+pub async fn run_pipeline<S, K>(mut source: S, storage: Arc<crate::storage::FractalStorage>, mut sink: K) -> Result<Cursor>
+where
+    S: Source,
+    K: Sink,
+{
+    use crate::storage::{ContributionStorage, NodeStorage, StoreOutcome};
+
+    while let Some(contribution) = source.next().await? {
+        info!("pipeline[{}]: ingested contribution {}", source.name(), contribution.id);
+        let node_id = contribution.node_id.clone();
+        if let StoreOutcome::RejectedBelowThreshold = storage.store_contribution(contribution).await? {
+            warn!("pipeline[{}]: contribution for {} rejected below resonance threshold", source.name(), node_id);
+            continue;
+        }
+        if let Some(node) = storage.get_node(&node_id).await {
+            if let Err(e) = sink.send(&node).await {
+                warn!("pipeline[{}]: sink {} failed: {}", source.name(), sink.name(), e);
+            }
+        }
+    }
+
+    let cursor = source.cursor();
+    storage.save_cursor(source.name(), &cursor).await?;
+    Ok(cursor)
+}
+
+/// Runs several `source -> sink` pipelines concurrently, returning each
+/// source's final cursor in the same order once every pipeline has drained.
+pub async fn run_pipelines_concurrently<S, K>(
+    pipelines: Vec<(S, Arc<crate::storage::FractalStorage>, K)>,
+) -> Vec<Result<Cursor>>
+where
+    S: Source + 'static,
+    K: Sink + 'static,
+{
+    let mut handles = Vec::with_capacity(pipelines.len());
+    for (source, storage, sink) in pipelines {
+        handles.push(tokio::spawn(run_pipeline(source, storage, sink)));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await.unwrap_or_else(|e| Err(anyhow::anyhow!("pipeline task panicked: {}", e))));
+    }
+    results
+}
@@ -0,0 +1,455 @@
+use crate::models::{ContextType, FractalNode};
+use anyhow::{Result, anyhow};
+
+// ============================================================================
+// QUERY DSL - `resonance >= 0.8 AND context IN (scientific, water)
+//              AND archetype CONTAINS "Flow" ORDER BY resonance DESC LIMIT 10`
+// ============================================================================
+//
+// A small hand-rolled tokenizer + recursive-descent parser, the same
+// from-scratch approach this codebase takes for the SigV4 request signing
+// and protobuf wire encoding elsewhere, rather than pulling in `pest`/`nom`
+// for one expression grammar. `FractalStorage::query` is the only entry
+// point: it parses, validates field names, and evaluates against the
+// in-memory node map.
+
+// ----------------------------------------------------------------------
+// TOKENIZER
+// ----------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    String(String),
+    Ge,
+    Le,
+    Ne,
+    Eq,
+    Gt,
+    Lt,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else if c == '>' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::Ge);
+            i += 2;
+        } else if c == '<' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::Le);
+            i += 2;
+        } else if c == '!' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::Ne);
+            i += 2;
+        } else if c == '>' {
+            tokens.push(Token::Gt);
+            i += 1;
+        } else if c == '<' {
+            tokens.push(Token::Lt);
+            i += 1;
+        } else if c == '=' {
+            tokens.push(Token::Eq);
+            i += 1;
+        } else if c == '"' {
+            let mut value = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                value.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(anyhow!("unterminated string literal"));
+            }
+            i += 1; // closing quote
+            tokens.push(Token::String(value));
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token::Number(text.parse().map_err(|_| anyhow!("invalid number literal '{}'", text))?));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token::Ident(text));
+        } else {
+            return Err(anyhow!("unexpected character '{}' in query", c));
+        }
+    }
+
+    Ok(tokens)
+}
+
+// ----------------------------------------------------------------------
+// AST
+// ----------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Ge,
+    Le,
+    Gt,
+    Lt,
+}
+
+#[derive(Debug, Clone)]
+enum Predicate {
+    Resonance(CompareOp, f64),
+    FractalLevel(CompareOp, u32),
+    ContextIn(Vec<String>),
+    ArchetypeContains(String),
+    WaterStateIs(String),
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Predicate(Predicate),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OrderField {
+    Resonance,
+    FractalLevel,
+}
+
+#[derive(Debug, Clone)]
+struct OrderBy {
+    field: OrderField,
+    descending: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Query {
+    expr: Option<Expr>,
+    order_by: Option<OrderBy>,
+    limit: Option<usize>,
+}
+
+// ----------------------------------------------------------------------
+// PARSER (recursive descent, one token of lookahead)
+// ----------------------------------------------------------------------
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        match self.advance() {
+            Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword) => Ok(()),
+            other => Err(anyhow!("expected '{}', found {:?}", keyword, other)),
+        }
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword))
+    }
+
+    fn parse_query(&mut self) -> Result<Query> {
+        let expr = if self.peek().is_some() && !self.peek_keyword("order") && !self.peek_keyword("limit") {
+            Some(self.parse_or()?)
+        } else {
+            None
+        };
+
+        let order_by = if self.peek_keyword("order") {
+            self.expect_keyword("order")?;
+            self.expect_keyword("by")?;
+            let field = match self.advance() {
+                Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case("resonance") => OrderField::Resonance,
+                Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case("fractal_level") => OrderField::FractalLevel,
+                other => return Err(anyhow!("ORDER BY supports resonance/fractal_level, found {:?}", other)),
+            };
+            let descending = if self.peek_keyword("desc") {
+                self.expect_keyword("desc")?;
+                true
+            } else if self.peek_keyword("asc") {
+                self.expect_keyword("asc")?;
+                false
+            } else {
+                false
+            };
+            Some(OrderBy { field, descending })
+        } else {
+            None
+        };
+
+        let limit = if self.peek_keyword("limit") {
+            self.expect_keyword("limit")?;
+            match self.advance() {
+                Some(Token::Number(n)) if n >= 0.0 => Some(n as usize),
+                other => return Err(anyhow!("LIMIT expects a non-negative number, found {:?}", other)),
+            }
+        } else {
+            None
+        };
+
+        if let Some(token) = self.peek() {
+            return Err(anyhow!("unexpected trailing token {:?}", token));
+        }
+
+        Ok(Query { expr, order_by, limit })
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.peek_keyword("or") {
+            self.expect_keyword("or")?;
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_unary()?;
+        while self.peek_keyword("and") {
+            self.expect_keyword("and")?;
+            let right = self.parse_unary()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.peek_keyword("not") {
+            self.expect_keyword("not")?;
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        if matches!(self.peek(), Some(Token::LParen)) {
+            self.advance();
+            let expr = self.parse_or()?;
+            match self.advance() {
+                Some(Token::RParen) => Ok(expr),
+                other => Err(anyhow!("expected ')', found {:?}", other)),
+            }
+        } else {
+            self.parse_predicate()
+        }
+    }
+
+    fn parse_predicate(&mut self) -> Result<Expr> {
+        let field = match self.advance() {
+            Some(Token::Ident(ident)) => ident,
+            other => return Err(anyhow!("expected a field name, found {:?}", other)),
+        };
+
+        let predicate = match field.as_str() {
+            "resonance" => Predicate::Resonance(self.parse_compare_op()?, self.parse_number()?),
+            "fractal_level" => Predicate::FractalLevel(self.parse_compare_op()?, self.parse_number()? as u32),
+            "context" => {
+                self.expect_keyword("in")?;
+                Predicate::ContextIn(self.parse_ident_list()?)
+            }
+            "archetype" => {
+                self.expect_keyword("contains")?;
+                Predicate::ArchetypeContains(self.parse_string()?)
+            }
+            "water_state" => {
+                self.expect_compare_eq()?;
+                Predicate::WaterStateIs(self.parse_ident_or_string()?)
+            }
+            other => {
+                return Err(anyhow!(
+                    "unknown field '{}': expected one of resonance, fractal_level, context, archetype, water_state",
+                    other
+                ))
+            }
+        };
+
+        Ok(Expr::Predicate(predicate))
+    }
+
+    fn parse_compare_op(&mut self) -> Result<CompareOp> {
+        match self.advance() {
+            Some(Token::Ge) => Ok(CompareOp::Ge),
+            Some(Token::Le) => Ok(CompareOp::Le),
+            Some(Token::Ne) => Ok(CompareOp::Ne),
+            Some(Token::Eq) => Ok(CompareOp::Eq),
+            Some(Token::Gt) => Ok(CompareOp::Gt),
+            Some(Token::Lt) => Ok(CompareOp::Lt),
+            other => Err(anyhow!("expected a comparison operator, found {:?}", other)),
+        }
+    }
+
+    fn expect_compare_eq(&mut self) -> Result<()> {
+        match self.advance() {
+            Some(Token::Eq) => Ok(()),
+            other => Err(anyhow!("expected '=', found {:?}", other)),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<f64> {
+        match self.advance() {
+            Some(Token::Number(n)) => Ok(n),
+            other => Err(anyhow!("expected a number, found {:?}", other)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String> {
+        match self.advance() {
+            Some(Token::String(s)) => Ok(s),
+            other => Err(anyhow!("expected a string literal, found {:?}", other)),
+        }
+    }
+
+    fn parse_ident_or_string(&mut self) -> Result<String> {
+        match self.advance() {
+            Some(Token::String(s)) => Ok(s),
+            Some(Token::Ident(s)) => Ok(s),
+            other => Err(anyhow!("expected a value, found {:?}", other)),
+        }
+    }
+
+    fn parse_ident_list(&mut self) -> Result<Vec<String>> {
+        match self.advance() {
+            Some(Token::LParen) => {}
+            other => return Err(anyhow!("expected '(', found {:?}", other)),
+        }
+
+        let mut values = Vec::new();
+        loop {
+            match self.advance() {
+                Some(Token::Ident(ident)) => values.push(ident.to_lowercase()),
+                other => return Err(anyhow!("expected a context name, found {:?}", other)),
+            }
+            match self.peek() {
+                Some(Token::Comma) => {
+                    self.advance();
+                }
+                Some(Token::RParen) => {
+                    self.advance();
+                    break;
+                }
+                other => return Err(anyhow!("expected ',' or ')', found {:?}", other)),
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// Parse `input` into a `Query`, validating field names along the way.
+pub fn parse(input: &str) -> Result<Query> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    parser.parse_query()
+}
+
+// ----------------------------------------------------------------------
+// EVALUATION
+// ----------------------------------------------------------------------
+
+fn context_category(context: &ContextType) -> &'static str {
+    match context {
+        ContextType::Scientific(_) => "scientific",
+        ContextType::Symbolic(_) => "symbolic",
+        ContextType::Water(_) => "water",
+        ContextType::Hybrid(_) => "hybrid",
+    }
+}
+
+fn compare(op: CompareOp, lhs: f64, rhs: f64) -> bool {
+    match op {
+        CompareOp::Eq => lhs == rhs,
+        CompareOp::Ne => lhs != rhs,
+        CompareOp::Ge => lhs >= rhs,
+        CompareOp::Le => lhs <= rhs,
+        CompareOp::Gt => lhs > rhs,
+        CompareOp::Lt => lhs < rhs,
+    }
+}
+
+fn eval_predicate(predicate: &Predicate, node: &FractalNode) -> bool {
+    match predicate {
+        Predicate::Resonance(op, value) => compare(*op, node.resonance, *value),
+        Predicate::FractalLevel(op, value) => compare(*op, node.fractal_level as f64, *value as f64),
+        Predicate::ContextIn(categories) => {
+            node.contexts.iter().any(|context| categories.iter().any(|c| c == context_category(context)))
+        }
+        Predicate::ArchetypeContains(needle) => {
+            node.archetype.iter().any(|a| a.to_lowercase().contains(&needle.to_lowercase()))
+        }
+        Predicate::WaterStateIs(expected) => format!("{:?}", node.water_state).eq_ignore_ascii_case(expected),
+    }
+}
+
+fn eval_expr(expr: &Expr, node: &FractalNode) -> bool {
+    match expr {
+        Expr::Predicate(predicate) => eval_predicate(predicate, node),
+        Expr::And(left, right) => eval_expr(left, node) && eval_expr(right, node),
+        Expr::Or(left, right) => eval_expr(left, node) || eval_expr(right, node),
+        Expr::Not(inner) => !eval_expr(inner, node),
+    }
+}
+
+/// Run a parsed `Query` against `nodes`, applying its filter, ordering, and
+/// limit in that order.
+pub fn execute(query: &Query, nodes: &[FractalNode]) -> Vec<FractalNode> {
+    let mut matching: Vec<FractalNode> = nodes
+        .iter()
+        .filter(|node| query.expr.as_ref().map_or(true, |expr| eval_expr(expr, node)))
+        .cloned()
+        .collect();
+
+    if let Some(order_by) = &query.order_by {
+        matching.sort_by(|a, b| {
+            let (a_key, b_key) = match order_by.field {
+                OrderField::Resonance => (a.resonance, b.resonance),
+                OrderField::FractalLevel => (a.fractal_level as f64, b.fractal_level as f64),
+            };
+            let ordering = a_key.partial_cmp(&b_key).unwrap_or(std::cmp::Ordering::Equal);
+            if order_by.descending { ordering.reverse() } else { ordering }
+        });
+    }
+
+    if let Some(limit) = query.limit {
+        matching.truncate(limit);
+    }
+
+    matching
+}
@@ -0,0 +1,300 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+
+// ============================================================================
+// REACTIVE RESONANCE ENGINE - Leptos-style signals/effects for the fractal graph
+// ============================================================================
+//
+// `resonance` today is a static `f64` recomputed on demand. This module adds a
+// fine-grained reactive layer on top: writing a node's resonance signal marks
+// every derived computation that read it as dirty, and a single `batch` call
+// recomputes them all exactly once, in dependency order.
+
+/// Identifies a signal or derived computation by the fractal node id it belongs to.
+pub type NodeKey = String;
+
+/// An observable cell holding one node's resonance value.
+#[derive(Clone)]
+pub struct ResonanceSignal {
+    key: NodeKey,
+    value: Arc<RwLock<f64>>,
+    runtime: Arc<Runtime>,
+}
+
+impl ResonanceSignal {
+    pub fn get(&self) -> f64 {
+        self.runtime.record_read(&self.key);
+        *self.value.read().unwrap()
+    }
+
+    pub fn set(&self, new_value: f64) {
+        *self.value.write().unwrap() = new_value;
+        self.runtime.mark_dirty(&self.key);
+    }
+}
+
+type EffectFn = Arc<dyn Fn(&Runtime) + Send + Sync>;
+
+#[derive(Clone)]
+struct Effect {
+    key: NodeKey,
+    run: EffectFn,
+}
+
+/// Tracks signals, their dependents, and schedules recomputation.
+///
+/// Each derived computation records, during its own evaluation, which signals
+/// it read (`record_read`); writing a signal (`mark_dirty`) marks every
+/// dependent as needing recomputation. `batch` coalesces writes so diamond
+/// dependencies (two derived values sharing an upstream signal, feeding a
+/// third) each run at most once per pass.
+pub struct Runtime {
+    signals: Mutex<HashMap<NodeKey, Arc<RwLock<f64>>>>,
+    effects: Mutex<Vec<Effect>>,
+    /// dependency -> set of effect keys that read it last time they ran
+    dependents: Mutex<HashMap<NodeKey, HashSet<NodeKey>>>,
+    dirty: Mutex<HashSet<NodeKey>>,
+    /// The effect currently being evaluated, so `record_read` knows who's asking.
+    current_effect: Mutex<Option<NodeKey>>,
+    batch_depth: Mutex<u32>,
+}
+
+impl Runtime {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            signals: Mutex::new(HashMap::new()),
+            effects: Mutex::new(Vec::new()),
+            dependents: Mutex::new(HashMap::new()),
+            dirty: Mutex::new(HashSet::new()),
+            current_effect: Mutex::new(None),
+            batch_depth: Mutex::new(0),
+        })
+    }
+
+    pub fn signal(self: &Arc<Self>, key: impl Into<String>, initial: f64) -> ResonanceSignal {
+        let key = key.into();
+        let cell = Arc::new(RwLock::new(initial));
+        self.signals.lock().unwrap().insert(key.clone(), cell.clone());
+        ResonanceSignal { key, value: cell, runtime: self.clone() }
+    }
+
+    /// The signal already created for `key` via `signal`, if any - so a
+    /// caller that only has a key (not a `ResonanceSignal` it kept around)
+    /// can still write through the same cell instead of creating a second,
+    /// disconnected one. See `storage::sync_resonance_signal`.
+    pub fn get_signal(self: &Arc<Self>, key: &str) -> Option<ResonanceSignal> {
+        let cell = self.signals.lock().unwrap().get(key)?.clone();
+        Some(ResonanceSignal { key: key.to_string(), value: cell, runtime: self.clone() })
+    }
+
+    /// Every key a signal has ever been created for. Includes keys whose
+    /// node was later deleted - `Runtime` has no signal unregister, the same
+    /// limitation documented on its effects.
+    pub fn signal_keys(&self) -> Vec<NodeKey> {
+        self.signals.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn record_read(&self, signal_key: &NodeKey) {
+        if let Some(effect_key) = self.current_effect.lock().unwrap().clone() {
+            self.dependents
+                .lock()
+                .unwrap()
+                .entry(signal_key.clone())
+                .or_insert_with(HashSet::new)
+                .insert(effect_key);
+        }
+    }
+
+    fn mark_dirty(&self, signal_key: &NodeKey) {
+        self.dirty.lock().unwrap().insert(signal_key.clone());
+        if *self.batch_depth.lock().unwrap() == 0 {
+            self.flush();
+        }
+    }
+
+    /// Register a derived computation keyed by node id, analogous to Leptos'
+    /// `map_keyed` effects: it re-runs whenever a signal it reads changes.
+    pub fn create_effect<F>(self: &Arc<Self>, key: impl Into<String>, f: F)
+    where
+        F: Fn(&Runtime) + Send + Sync + 'static,
+    {
+        let key = key.into();
+        self.effects.lock().unwrap().push(Effect { key: key.clone(), run: Arc::new(f) });
+        self.run_effect(&key);
+    }
+
+    /// Coalesce every signal write inside `f` into a single propagation pass.
+    pub fn batch<F, R>(self: &Arc<Self>, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        *self.batch_depth.lock().unwrap() += 1;
+        let result = f();
+        let mut depth = self.batch_depth.lock().unwrap();
+        *depth -= 1;
+        let is_outermost = *depth == 0;
+        drop(depth);
+        if is_outermost {
+            self.flush();
+        }
+        result
+    }
+
+    /// Recompute every dirty effect exactly once, in topological order, with
+    /// cycles broken deterministically by node id so the walk always terminates.
+    fn flush(&self) {
+        let mut dirty = self.dirty.lock().unwrap();
+        if dirty.is_empty() {
+            return;
+        }
+        let seeds: Vec<NodeKey> = dirty.drain().collect();
+        drop(dirty);
+
+        let order = self.topological_order(&seeds);
+        for effect_key in order {
+            self.run_effect(&effect_key);
+        }
+    }
+
+    /// Breadth-first walk from the dirty signals/effects to their dependents,
+    /// deduplicating so a diamond-shaped dependency runs only once, and
+    /// breaking cycles by refusing to revisit a key already emitted (ties
+    /// broken by ascending node id for a stable order).
+    fn topological_order(&self, seeds: &[NodeKey]) -> Vec<NodeKey> {
+        let dependents = self.dependents.lock().unwrap();
+        let mut visited: HashSet<NodeKey> = HashSet::new();
+        let mut order: Vec<NodeKey> = Vec::new();
+        let mut queue: VecDeque<NodeKey> = seeds.iter().cloned().collect();
+
+        while let Some(key) = queue.pop_front() {
+            if !visited.insert(key.clone()) {
+                continue; // already scheduled: diamond dependency or a back-edge cycle
+            }
+            if let Some(next) = dependents.get(&key) {
+                let mut next_sorted: Vec<&NodeKey> = next.iter().collect();
+                next_sorted.sort(); // stable priority by node id when breaking cycles
+                for dependent in next_sorted {
+                    if !visited.contains(dependent) {
+                        queue.push_back(dependent.clone());
+                    }
+                }
+            }
+            order.push(key);
+        }
+
+        order
+    }
+
+    /// Derive an edge's resonance strength from its two endpoint signals,
+    /// re-firing only when `left` or `right` changes. `compute` receives the
+    /// two current values and returns the edge strength to store in `sink`.
+    pub fn derive_edge(
+        self: &Arc<Self>,
+        edge_key: impl Into<String>,
+        left: ResonanceSignal,
+        right: ResonanceSignal,
+        sink: Arc<RwLock<f64>>,
+        compute: impl Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    ) {
+        self.create_effect(edge_key, move |_rt| {
+            let strength = compute(left.get(), right.get());
+            *sink.write().unwrap() = strength;
+        });
+    }
+
+    fn run_effect(&self, key: &NodeKey) {
+        let run = {
+            let effects = self.effects.lock().unwrap();
+            effects.iter().find(|e| &e.key == key).map(|e| e.run.clone())
+        };
+        if let Some(run) = run {
+            *self.current_effect.lock().unwrap() = Some(key.clone());
+            run(self);
+            *self.current_effect.lock().unwrap() = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn batch_coalesces_a_diamond_dependency_into_one_run() {
+        let rt = Runtime::new();
+        let a = rt.signal("a", 1.0);
+        let b = rt.signal("b", 2.0);
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let (a_for_effect, b_for_effect, runs_for_effect) = (a.clone(), b.clone(), runs.clone());
+        rt.create_effect("diamond", move |_| {
+            runs_for_effect.fetch_add(1, Ordering::SeqCst);
+            let _ = a_for_effect.get() + b_for_effect.get();
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 1); // initial registration run
+
+        rt.batch(|| {
+            a.set(10.0);
+            b.set(20.0);
+        });
+
+        // Both signals are read by the same effect; without coalescing this
+        // would run twice (once per write).
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn derive_edge_recomputes_from_both_endpoints() {
+        let rt = Runtime::new();
+        let left = rt.signal("left", 2.0);
+        let right = rt.signal("right", 3.0);
+        let sink = Arc::new(RwLock::new(0.0));
+
+        rt.derive_edge("left|right", left.clone(), right.clone(), sink.clone(), |a, b| a * b);
+        assert_eq!(*sink.read().unwrap(), 6.0);
+
+        left.set(5.0);
+        assert_eq!(*sink.read().unwrap(), 15.0);
+    }
+
+    #[test]
+    fn get_signal_writes_through_the_same_cell_signal_created() {
+        let rt = Runtime::new();
+        let original = rt.signal("node-a", 1.0);
+
+        let looked_up = rt.get_signal("node-a").expect("signal was just created");
+        looked_up.set(42.0);
+        assert_eq!(original.get(), 42.0);
+
+        assert!(rt.get_signal("missing").is_none());
+        assert_eq!(rt.signal_keys(), vec!["node-a".to_string()]);
+    }
+
+    #[test]
+    fn topological_order_breaks_cycles_and_visits_each_key_once() {
+        let rt = Runtime::new();
+        let a = rt.signal("x", 1.0);
+
+        let order_seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (order_for_x, a_for_x) = (order_seen.clone(), a.clone());
+        rt.create_effect("x", move |_| {
+            order_for_x.lock().unwrap().push("x");
+            let _ = a_for_x.get();
+        });
+
+        // "x" depends on itself transitively (its own effect reads signal
+        // "x"); flushing it must terminate and run "x" exactly once, not loop.
+        // The first "x" is the initial registration run by `create_effect`.
+        rt.mark_dirty_for_test("x");
+        assert_eq!(order_seen.lock().unwrap().as_slice(), ["x", "x"]);
+    }
+
+    impl Runtime {
+        /// Test-only hook: flush as if signal `key` had just been written,
+        /// without needing a real `ResonanceSignal::set` call.
+        fn mark_dirty_for_test(&self, key: &str) {
+            self.mark_dirty(&key.to_string());
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+// ============================================================================
+// SCHEMA VERSIONING - Forward migration for persisted nodes/contributions
+// ============================================================================
+//
+// `models::FractalNode`/`models::Contribution` now carry a `schema_version`
+// field, defaulted to `legacy_schema_version()` for any record persisted
+// before it existed. Loading a record means: read it as an untyped `Value`,
+// walk it through every migrator from its version up to
+// `CURRENT_SCHEMA_VERSION`, then deserialize into the concrete struct.
+//
+// `v1` freezes the struct layout as it existed before this field was added.
+// The only migrator so far, `migrate_v1_to_v2`, inserts `schema_version`
+// into the untyped `Value` and does not go through the `v1` types. Never
+// edit `v1` once a `v2` module exists - add the next one instead and
+// register another migrator.
+
+/// The version every in-memory `FractalNode`/`Contribution` is constructed
+/// at. Bump this and add a migrator whenever the struct's on-disk shape
+/// changes.
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Records persisted before `schema_version` existed have no value for it;
+/// treat them as version 1, the layout frozen in `v1` below.
+pub fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// The struct layout as it was before `schema_version` was introduced.
+/// Not referenced by `migrate_v1_to_v2`, which works on the untyped `Value`.
+pub mod v1 {
+    use crate::models::ContextType;
+    use crate::models::FlowState;
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct FractalNode {
+        pub id: String,
+        pub name: String,
+        pub water_state: FlowState,
+        pub archetype: Vec<String>,
+        pub resonance: f64,
+        pub fractal_level: u32,
+        pub contexts: Vec<ContextType>,
+        pub parent_id: Option<String>,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+        pub metadata: HashMap<String, serde_json::Value>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct Contribution {
+        pub id: String,
+        pub node_id: String,
+        pub user_id: String,
+        pub content: String,
+        pub resonance: f64,
+        pub timestamp: DateTime<Utc>,
+        pub fractal_context: Option<ContextType>,
+        pub metadata: HashMap<String, serde_json::Value>,
+    }
+}
+
+/// One step in a migration chain: upgrade a record from version `from` to
+/// `from + 1`.
+type Migrator = fn(Value) -> Result<Value>;
+
+fn migrate_v1_to_v2(mut value: Value) -> Result<Value> {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("schema_version".to_string(), Value::from(2));
+    }
+    Ok(value)
+}
+
+/// Migrators shared by every persisted record type, keyed by the version
+/// they upgrade *from*. `FractalNode` and `Contribution` both only gained
+/// `schema_version` in the v1 -> v2 step, so one chain covers both.
+fn migrators() -> Vec<(u32, Migrator)> {
+    vec![(1, migrate_v1_to_v2)]
+}
+
+/// Parse `bytes` as JSON, walk it through every applicable migrator up to
+/// `CURRENT_SCHEMA_VERSION`, then deserialize into `T`.
+pub fn load_versioned<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let mut value: Value = serde_json::from_slice(bytes)?;
+    let mut version = value.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+
+    for (from, migrate) in migrators() {
+        if version == from {
+            value = migrate(value)?;
+            version += 1;
+        }
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
@@ -1,15 +1,21 @@
+use crate::backend::{BackendConfig, ObjectBackend, StorageBackend};
 use crate::models::{
     FractalNode, Contribution, StorageStats,
     FlowState, ContextType, ScientificContext, SymbolicContext, WaterContext,
-    calculate_resonance_multiplier
+    calculate_resonance_multiplier, resonance_edge_strength
 };
+use crate::merkle::AppendMerkleTree;
+use crate::oplog::{Checkpoint, OpLog, Operation, ShouldCheckpoint};
+use crate::reactive;
 use anyhow::{Result, anyhow};
+use futures_util::{Stream, StreamExt};
 use serde_json;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tokio_stream::wrappers::BroadcastStream;
 use sha2::{Sha256, Digest};
 use hex;
 use tracing::{info, warn, error};
@@ -20,7 +26,7 @@ use tracing::{info, warn, error};
 
 /// Trait for storing and retrieving fractal nodes
 pub trait NodeStorage {
-    async fn store_node(&self, node: &FractalNode) -> Result<()>;
+    async fn store_node(&self, node: &FractalNode) -> Result<StoreOutcome>;
     async fn get_node(&self, node_id: &str) -> Option<FractalNode>;
     async fn get_all_nodes(&self) -> Vec<FractalNode>;
     async fn delete_node(&self, node_id: &str) -> Result<()>;
@@ -28,7 +34,7 @@ pub trait NodeStorage {
 
 /// Trait for storing and retrieving contributions
 pub trait ContributionStorage {
-    async fn store_contribution(&self, contribution: Contribution) -> Result<serde_json::Value>;
+    async fn store_contribution(&self, contribution: Contribution) -> Result<StoreOutcome>;
     async fn get_contribution(&self, content_hash: &str) -> Option<Contribution>;
     async fn get_node_contributions(&self, node_id: &str) -> serde_json::Value;
     async fn get_user_contributions(&self, user_id: &str) -> serde_json::Value;
@@ -48,10 +54,64 @@ pub trait StorageMetadata {
     async fn get_storage_size(&self) -> u64;
 }
 
+/// Emitted by `store_node`/`delete_node`/`store_contribution`/
+/// `add_node_to_context` so live consumers can react to changes instead of
+/// polling `get_all_nodes`. See `FractalStorage::subscribe`/`subscribe_context`.
+#[derive(Debug, Clone)]
+pub enum StorageEvent {
+    NodeStored { id: String, fractal_level: u32, contexts: Vec<ContextType> },
+    NodeDeleted { id: String },
+    ContributionStored { node_id: String, resonance: f64 },
+    /// A contribution was removed by `StorageConfig::eviction` to stay within
+    /// `max_contributions`, not by an explicit delete (there is none).
+    ContributionEvicted { id: String },
+    NodeContextAdded { context: ContextType, node_id: String },
+}
+
+impl StorageEvent {
+    /// Whether this event is about a node/context association involving
+    /// `context`. `ContributionStored`/`NodeDeleted`/`ContributionEvicted`
+    /// carry no context information, so they never match a context filter.
+    fn touches_context(&self, context: &ContextType) -> bool {
+        match self {
+            StorageEvent::NodeStored { contexts, .. } => contexts.contains(context),
+            StorageEvent::NodeContextAdded { context: event_context, .. } => event_context == context,
+            StorageEvent::NodeDeleted { .. }
+            | StorageEvent::ContributionStored { .. }
+            | StorageEvent::ContributionEvicted { .. } => false,
+        }
+    }
+}
+
 // ============================================================================
 // CONFIGURATION - Flexible settings like water adapting to containers
 // ============================================================================
 
+/// Which entries to remove first when `max_nodes`/`max_contributions` is
+/// reached and room must be made for a new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Policy {
+    /// Remove whichever entries have the lowest `resonance`.
+    #[default]
+    LowestResonanceFirst,
+    /// Remove whichever entries are oldest (`created_at`/`timestamp`).
+    OldestFirst,
+}
+
+/// What happened to a `store_node`/`store_contribution` call once
+/// `StorageConfig`'s capacity and resonance policies were applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreOutcome {
+    /// Accepted; capacity wasn't exceeded.
+    Stored,
+    /// Accepted, after evicting `evicted_ids` under `StorageConfig::eviction`
+    /// to stay within `max_nodes`/`max_contributions`.
+    Evicted { evicted_ids: Vec<String> },
+    /// Rejected outright: `resonance` was below
+    /// `StorageConfig::resonance_threshold`. Nothing was stored or evicted.
+    RejectedBelowThreshold,
+}
+
 #[derive(Debug, Clone)]
 pub struct StorageConfig {
     pub base_path: String,
@@ -61,6 +121,18 @@ pub struct StorageConfig {
     pub fractal_levels: Vec<u32>,
     pub supported_contexts: Vec<ContextType>,
     pub resonance_threshold: f64,
+    /// Where `store_node`/`store_contribution`/`delete_node` durably persist
+    /// bytes alongside the in-memory maps: local files, an embedded SQLite
+    /// file, or an S3-compatible bucket. Defaults to local files under
+    /// `./rust-fractal-storage`, the same path as the default `base_path`.
+    pub backend: BackendConfig,
+    /// How many operations accumulate in the on-disk log before a full
+    /// checkpoint is written. Smaller values make recovery faster (less to
+    /// replay) at the cost of more frequent full-state writes.
+    pub checkpoint_interval: usize,
+    /// Which entries `store_node`/`store_contribution` evict first once
+    /// `max_nodes`/`max_contributions` is reached.
+    pub eviction: Policy,
 }
 
 impl Default for StorageConfig {
@@ -83,6 +155,9 @@ impl Default for StorageConfig {
                 ContextType::Water(WaterContext::Coherence),
             ],
             resonance_threshold: 0.5,
+            backend: BackendConfig::default(),
+            checkpoint_interval: 64,
+            eviction: Policy::default(),
         }
     }
 }
@@ -97,52 +172,328 @@ pub struct FractalStorage {
     contributions: Arc<RwLock<HashMap<String, Contribution>>>,
     stats: Arc<RwLock<StorageStats>>,
     node_contexts: Arc<RwLock<HashMap<ContextType, Vec<String>>>>, // Context -> Node IDs
+    /// Broadcasts every contribution accepted through `store_contribution`,
+    /// whether it arrived via `/inbox`, REST, or a federation pull, so GraphQL
+    /// subscriptions and other live consumers see one unified event stream.
+    contribution_events: tokio::sync::broadcast::Sender<Contribution>,
+    /// Broadcasts a `StorageEvent` for every node/contribution/context
+    /// mutation, so callers can react to changes instead of polling
+    /// `get_all_nodes`. Sibling of `contribution_events` above, same
+    /// broadcast-channel mechanism, one event type per kind of listener.
+    storage_events: tokio::sync::broadcast::Sender<StorageEvent>,
+    /// Durable byte persistence for nodes/contributions, written through
+    /// alongside the in-memory maps above. Selected by `config.backend`.
+    object_backend: ObjectBackend,
+    /// Append-only log of every mutation plus periodic full-state
+    /// checkpoints, so a crash loses nothing accepted since the last write.
+    /// With a `Local` backend this is the source of truth on startup; with
+    /// `Sqlite`/`S3` the backend is consulted first. See `recover_or_initialize`.
+    oplog: OpLog,
+    /// Tamper-evident commitment over every accepted contribution's content
+    /// hash, appended to by `store_contribution` and rebuilt on load from
+    /// `contribution_tree_leaves` - never from the live `contributions` map,
+    /// since eviction removes entries from that map but the tree itself is
+    /// append-only and must keep committing to evicted contributions too.
+    contribution_tree: Arc<RwLock<AppendMerkleTree>>,
+    /// Content hash of every contribution ever accepted through
+    /// `store_contribution`, in the exact order appended to
+    /// `contribution_tree`. Unlike `contributions`, eviction never removes an
+    /// entry here - this is what `rebuild_contribution_tree` replays to
+    /// reproduce the same root `store_contribution` built live.
+    contribution_tree_leaves: Arc<RwLock<Vec<String>>>,
+    /// Drives the per-node resonance signals and derived edge strengths
+    /// below, and is itself the source of truth for which node ids have a
+    /// signal - see `sync_resonance_signal`, which looks signals up through
+    /// `reactive.get_signal` rather than keeping a second, parallel map.
+    reactive: Arc<reactive::Runtime>,
+    /// Edge strength between every pair of nodes that both have a resonance
+    /// signal, recomputed by `reactive` per `models::resonance_edge_strength`
+    /// the moment either endpoint's signal changes (0 once they're too far
+    /// apart to resonate, per `Resonant::can_resonate_with`'s contract).
+    /// Keyed by `edge_key`, so each unordered pair has exactly one entry.
+    edge_strengths: Arc<RwLock<HashMap<String, Arc<std::sync::RwLock<f64>>>>>,
 }
 
 impl FractalStorage {
+    /// Convenience constructor for a purely local deployment: both the oplog
+    /// and the object backend live under `storage_path`. To select the
+    /// `Sqlite`/`S3` backend instead, build a `StorageConfig` directly and use
+    /// `with_config`.
     pub async fn new(storage_path: &str) -> Result<Self> {
         info!("Creating new FractalStorage with path: {}", storage_path);
-        
+
         let config = StorageConfig {
             base_path: storage_path.to_string(),
+            backend: BackendConfig::Local { base_path: storage_path.to_string() },
             ..Default::default()
         };
         
         info!("Storage config created");
-        
+
+        let object_backend = ObjectBackend::from_config(&config.backend)?;
+        let oplog = OpLog::open(&config.base_path, config.checkpoint_interval)?;
+
         let storage = Self {
             config,
             nodes: Arc::new(RwLock::new(HashMap::new())),
             contributions: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(StorageStats::default())),
             node_contexts: Arc::new(RwLock::new(HashMap::new())),
+            contribution_events: tokio::sync::broadcast::channel(256).0,
+            storage_events: tokio::sync::broadcast::channel(256).0,
+            object_backend,
+            oplog,
+            contribution_tree: Arc::new(RwLock::new(AppendMerkleTree::new())),
+            contribution_tree_leaves: Arc::new(RwLock::new(Vec::new())),
+            reactive: reactive::Runtime::new(),
+            edge_strengths: Arc::new(RwLock::new(HashMap::new())),
         };
-        
+
         info!("Storage structure created, ensuring directories exist...");
         storage.ensure_storage_exists()?;
-        
-        info!("Directories ready, initializing fractal nodes...");
-        storage.initialize_fractal_nodes().await?;
-        
-        info!("Fractal nodes initialized successfully");
+
+        info!("Directories ready, recovering existing state...");
+        storage.recover_or_initialize().await?;
+
+        storage.rebuild_contribution_tree().await?;
+
+        info!("Fractal storage ready");
         Ok(storage)
     }
 
     pub async fn with_config(config: StorageConfig) -> Result<Self> {
+        let object_backend = ObjectBackend::from_config(&config.backend)?;
+        let oplog = OpLog::open(&config.base_path, config.checkpoint_interval)?;
+
         let storage = Self {
             config,
             nodes: Arc::new(RwLock::new(HashMap::new())),
             contributions: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(StorageStats::default())),
             node_contexts: Arc::new(RwLock::new(HashMap::new())),
+            contribution_events: tokio::sync::broadcast::channel(256).0,
+            storage_events: tokio::sync::broadcast::channel(256).0,
+            object_backend,
+            oplog,
+            contribution_tree: Arc::new(RwLock::new(AppendMerkleTree::new())),
+            contribution_tree_leaves: Arc::new(RwLock::new(Vec::new())),
+            reactive: reactive::Runtime::new(),
+            edge_strengths: Arc::new(RwLock::new(HashMap::new())),
         };
-        
+
         storage.ensure_storage_exists()?;
-        storage.initialize_fractal_nodes().await?;
-        
+
+        storage.recover_or_initialize().await?;
+
+        storage.rebuild_contribution_tree().await?;
+
         Ok(storage)
     }
 
+    /// Seed in-memory state on startup, in priority order. `object_backend.put`
+    /// always happens-before the matching `oplog.append` in `store_node`/
+    /// `store_contribution`, so the backend is never behind the oplog - but
+    /// for `BackendConfig::Local` the two live on the same disk, so the oplog
+    /// (a full mutation history, not just latest-value snapshots) is preferred
+    /// since it costs nothing extra to recover from. For a backend that may
+    /// `may_outlive_local_disk` (`Sqlite`/`S3`), the oplog may simply be gone
+    /// after a redeploy, so the backend is consulted first instead.
+    async fn recover_or_initialize(&self) -> Result<()> {
+        let recovered = if self.config.backend.may_outlive_local_disk() {
+            if self.load_from_backend().await? {
+                info!("Loaded existing nodes/contributions from backend");
+                true
+            } else if self.recover_from_oplog().await? {
+                info!("Recovered existing nodes/contributions from operation log");
+                true
+            } else {
+                false
+            }
+        } else if self.recover_from_oplog().await? {
+            info!("Recovered existing nodes/contributions from operation log");
+            true
+        } else if self.load_from_backend().await? {
+            info!("Loaded existing nodes/contributions from backend");
+            true
+        } else {
+            false
+        };
+
+        if !recovered {
+            info!("No existing data found, initializing fractal nodes...");
+            self.initialize_fractal_nodes().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuild in-memory state from the operation log: seed from the most
+    /// recent valid checkpoint (if any), then replay every operation logged
+    /// after it, strictly in sequence order. Returns `false` when the log is
+    /// empty, so the caller falls back to `load_from_backend`/
+    /// `initialize_fractal_nodes`.
+    async fn recover_from_oplog(&self) -> Result<bool> {
+        let recovered = self.oplog.recover()?;
+        if recovered.checkpoint.is_none() && recovered.ops.is_empty() {
+            return Ok(false);
+        }
+
+        if let Some(checkpoint) = recovered.checkpoint {
+            *self.nodes.write().await = checkpoint.nodes;
+            *self.contributions.write().await = checkpoint.contributions;
+            *self.node_contexts.write().await = checkpoint.node_contexts.into_iter().collect();
+            *self.contribution_tree_leaves.write().await = checkpoint.contribution_tree_leaf_hashes;
+        }
+
+        for logged in recovered.ops {
+            self.apply_operation(logged.op).await;
+        }
+
+        self.update_stats().await?;
+        Ok(true)
+    }
+
+    /// Apply one previously-logged operation directly to the in-memory maps,
+    /// without re-appending it to the log or writing through to
+    /// `object_backend` - used only during `recover_from_oplog` replay.
+    /// Idempotent: re-applying a `StoreNode`/`StoreContribution` for an
+    /// already-present id just overwrites it.
+    async fn apply_operation(&self, op: Operation) {
+        match op {
+            Operation::StoreNode(node) => {
+                self.nodes.write().await.insert(node.id.clone(), node);
+            }
+            Operation::DeleteNode(node_id) => {
+                self.nodes.write().await.remove(&node_id);
+            }
+            Operation::StoreContribution(contribution) => {
+                let content_hash = self.generate_content_hash(&contribution.content);
+                self.contribution_tree_leaves.write().await.push(content_hash);
+                self.contributions.write().await.insert(contribution.id.clone(), contribution);
+            }
+            Operation::DeleteContribution(id) => {
+                // Eviction removes it from the live map, but `contribution_tree_leaves`
+                // is append-only - it must keep replaying every leaf the live
+                // Merkle tree ever committed to, including this one.
+                self.contributions.write().await.remove(&id);
+            }
+            Operation::AddNodeToContext { context, node_id } => {
+                let mut contexts = self.node_contexts.write().await;
+                let ids = contexts.entry(context).or_insert_with(Vec::new);
+                if !ids.contains(&node_id) {
+                    ids.push(node_id);
+                }
+            }
+        }
+    }
+
+    /// Write a full checkpoint of the current in-memory state if `should`
+    /// says enough operations have accumulated since the last one.
+    async fn maybe_checkpoint(&self, should: ShouldCheckpoint) -> Result<()> {
+        let seq = match should {
+            ShouldCheckpoint::Yes(seq) => seq,
+            ShouldCheckpoint::No => return Ok(()),
+        };
+
+        let checkpoint = Checkpoint {
+            seq,
+            nodes: self.nodes.read().await.clone(),
+            contributions: self.contributions.read().await.clone(),
+            node_contexts: self.node_contexts.read().await.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            contribution_tree_leaf_hashes: self.contribution_tree_leaves.read().await.clone(),
+        };
+        self.oplog.write_checkpoint(checkpoint).await
+    }
+
+    /// Rebuild the contribution Merkle tree from `contribution_tree_leaves`,
+    /// in the exact order each was originally appended. Deliberately not
+    /// derived from `self.contributions`: eviction removes entries from that
+    /// map, but never from the append-only tree, so rebuilding from the map
+    /// would produce a different (smaller) root than the one `store_contribution`
+    /// built live.
+    async fn rebuild_contribution_tree(&self) -> Result<()> {
+        let leaves = self.contribution_tree_leaves.read().await.clone();
+
+        let mut tree = AppendMerkleTree::new();
+        for content_hash in &leaves {
+            if let Some(leaf) = content_hash_to_leaf(content_hash) {
+                tree.append(leaf);
+            }
+        }
+
+        let root = tree.root();
+        *self.contribution_tree.write().await = tree;
+
+        let mut stats = self.stats.write().await;
+        stats.contribution_root = root.map(hex::encode);
+        Ok(())
+    }
+
+    /// Sibling hashes and left/right positions proving `content_hash` is
+    /// included in the current contribution Merkle tree, or `None` if it
+    /// isn't a leaf (e.g. unknown hash).
+    pub async fn get_inclusion_proof(&self, content_hash: &str) -> Option<Vec<(crate::merkle::Hash, crate::merkle::Side)>> {
+        let leaf = content_hash_to_leaf(content_hash)?;
+        self.contribution_tree.read().await.get_inclusion_proof(&leaf)
+    }
+
+    /// The current Merkle root over every accepted contribution, hex-encoded.
+    pub async fn contribution_root(&self) -> Option<String> {
+        self.contribution_tree.read().await.root().map(hex::encode)
+    }
+
+    /// Load any nodes/contributions already persisted in the backend into the
+    /// in-memory maps, rebuilding `node_contexts` and `stats` from them.
+    /// Returns `false` (seeding from `initialize_fractal_nodes` instead) when
+    /// the backend has nothing yet, e.g. a fresh local directory or database.
+    async fn load_from_backend(&self) -> Result<bool> {
+        let mut loaded_nodes = Vec::new();
+        for key in self.object_backend.list("nodes").await? {
+            if let Some(bytes) = self.object_backend.get(&key).await? {
+                match crate::schema::load_versioned::<FractalNode>(&bytes) {
+                    Ok(node) => loaded_nodes.push(node),
+                    Err(e) => warn!("skipping unreadable stored node at {}: {}", key, e),
+                }
+            }
+        }
+
+        let mut loaded_contributions = Vec::new();
+        for key in self.object_backend.list("contributions").await? {
+            if let Some(bytes) = self.object_backend.get(&key).await? {
+                match crate::schema::load_versioned::<Contribution>(&bytes) {
+                    Ok(contribution) => loaded_contributions.push(contribution),
+                    Err(e) => warn!("skipping unreadable stored contribution at {}: {}", key, e),
+                }
+            }
+        }
+
+        if loaded_nodes.is_empty() && loaded_contributions.is_empty() {
+            return Ok(false);
+        }
+
+        {
+            let mut nodes = self.nodes.write().await;
+            for node in &loaded_nodes {
+                nodes.insert(node.id.clone(), node.clone());
+            }
+        }
+        {
+            let mut contributions = self.contributions.write().await;
+            for contribution in &loaded_contributions {
+                contributions.insert(contribution.id.clone(), contribution.clone());
+            }
+        }
+
+        for node in &loaded_nodes {
+            for context in &node.contexts {
+                self.add_node_to_context(context, &node.id).await?;
+            }
+        }
+
+        self.update_stats().await?;
+        Ok(true)
+    }
+
     fn ensure_storage_exists(&self) -> Result<()> {
         let path = Path::new(&self.config.base_path);
         if !path.exists() {
@@ -236,38 +587,191 @@ impl FractalStorage {
             base_node.add_context(context.clone());
             
             // Store context mapping
-            self.add_node_to_context(&context, &base_node.id).await;
+            self.add_node_to_context(&context, &base_node.id).await?;
         }
         
         for sym_context in &symbolic_contexts {
             let context = ContextType::Symbolic(sym_context.clone());
             base_node.add_context(context.clone());
             
-            self.add_node_to_context(&context, &base_node.id).await;
+            self.add_node_to_context(&context, &base_node.id).await?;
         }
         
         for wat_context in &water_contexts {
             let context = ContextType::Water(wat_context.clone());
             base_node.add_context(context.clone());
             
-            self.add_node_to_context(&context, &base_node.id).await;
+            self.add_node_to_context(&context, &base_node.id).await?;
         }
         
         Ok(())
     }
 
-    async fn add_node_to_context(&self, context: &ContextType, node_id: &str) {
+    async fn add_node_to_context(&self, context: &ContextType, node_id: &str) -> Result<()> {
         let mut contexts = self.node_contexts.write().await;
         contexts.entry(context.clone())
             .or_insert_with(Vec::new)
             .push(node_id.to_string());
+        drop(contexts);
+
+        let _ = self.storage_events.send(StorageEvent::NodeContextAdded {
+            context: context.clone(),
+            node_id: node_id.to_string(),
+        });
+
+        let should_checkpoint = self.oplog.append(Operation::AddNodeToContext {
+            context: context.clone(),
+            node_id: node_id.to_string(),
+        }).await?;
+        self.maybe_checkpoint(should_checkpoint).await
     }
 
-    fn generate_content_hash(&self, content: &str) -> String {
+    pub(crate) fn generate_content_hash(&self, content: &str) -> String {
         let mut hasher = Sha256::new();
         hasher.update(content.as_bytes());
         hex::encode(hasher.finalize())
     }
+
+    /// Push `node`'s resonance into the reactive layer: writes through its
+    /// existing `ResonanceSignal` if one already exists, or creates one and
+    /// wires up a derived edge strength to every other known live node, using
+    /// the same frequency-cutoff contract as `Resonant::resonance_strength`
+    /// (see `resonance_edge_strength`) rather than a metadata proxy - two
+    /// nodes sharing a context says nothing about whether their resonance
+    /// frequencies are actually close. See `edge_strength`.
+    async fn sync_resonance_signal(&self, node: &FractalNode) {
+        if let Some(signal) = self.reactive.get_signal(&node.id) {
+            self.reactive.batch(|| signal.set(node.resonance));
+            return;
+        }
+
+        let signal = self.reactive.signal(node.id.clone(), node.resonance);
+
+        let nodes = self.nodes.read().await;
+        let mut edges_to_add = Vec::new();
+        {
+            let edges = self.edge_strengths.read().await;
+            for other_id in self.reactive.signal_keys() {
+                if other_id == node.id || !nodes.contains_key(&other_id) {
+                    continue;
+                }
+                let key = edge_key(&node.id, &other_id);
+                if edges.contains_key(&key) {
+                    continue;
+                }
+                let Some(other_signal) = self.reactive.get_signal(&other_id) else { continue };
+                edges_to_add.push((key, other_signal));
+            }
+        }
+        drop(nodes);
+
+        for (key, other_signal) in edges_to_add {
+            let sink = Arc::new(std::sync::RwLock::new(0.0));
+            self.reactive.derive_edge(key.clone(), signal.clone(), other_signal, sink.clone(), |a, b| resonance_edge_strength(a, b));
+            self.edge_strengths.write().await.insert(key, sink);
+        }
+    }
+
+    /// Current derived resonance strength of the edge between `a` and `b`, if
+    /// both have been stored and neither has since been deleted or evicted.
+    /// See `sync_resonance_signal`.
+    pub async fn edge_strength(&self, a: &str, b: &str) -> Option<f64> {
+        let key = edge_key(a, b);
+        let edges = self.edge_strengths.read().await;
+        edges.get(&key).map(|sink| *sink.read().unwrap())
+    }
+
+    /// Drop every edge derived from `node_id`'s resonance signal, on delete
+    /// or eviction. The `Runtime` itself keeps the signal and its effects
+    /// registered (it has no unregister); only `edge_strength` stops
+    /// reporting the dropped edges.
+    async fn forget_resonance_signal(&self, node_id: &str) {
+        self.edge_strengths.write().await.retain(|key, _| !key.split('|').any(|id| id == node_id));
+    }
+
+    /// Persist a pipeline source's resume position so `run_pipeline` can pick
+    /// up where it left off after a restart. Keyed by `Source::name()`.
+    pub async fn save_cursor(&self, source_name: &str, cursor: &crate::pipeline::Cursor) -> Result<()> {
+        self.object_backend.put(&format!("cursors/{}.json", source_name), serde_json::to_vec(cursor)?).await
+    }
+
+    /// The resume position last saved for `source_name` via `save_cursor`, or
+    /// `None` if this source has never completed a `run_pipeline` call.
+    pub async fn load_cursor(&self, source_name: &str) -> Option<crate::pipeline::Cursor> {
+        let bytes = self.object_backend.get(&format!("cursors/{}.json", source_name)).await.ok()??;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    /// All stored contributions, most recent first. Used by federation sync
+    /// to decide what to push to peers.
+    pub async fn all_contributions(&self) -> Vec<Contribution> {
+        let contributions = self.contributions.read().await;
+        let mut all: Vec<Contribution> = contributions.values().cloned().collect();
+        all.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        all
+    }
+
+    /// Subscribe to every contribution accepted from this point on, regardless
+    /// of which endpoint accepted it.
+    pub fn subscribe_contributions(&self) -> tokio::sync::broadcast::Receiver<Contribution> {
+        self.contribution_events.subscribe()
+    }
+
+    /// Every node/contribution/context mutation from this point on. Supports
+    /// fan-out to as many concurrent consumers as want to listen.
+    pub fn subscribe(&self) -> impl Stream<Item = StorageEvent> {
+        BroadcastStream::new(self.storage_events.subscribe()).filter_map(|result| async move { result.ok() })
+    }
+
+    /// Same as `subscribe`, filtered to events that touch `context`.
+    pub fn subscribe_context(&self, context: &ContextType) -> impl Stream<Item = StorageEvent> {
+        let context = context.clone();
+        self.subscribe().filter(move |event| {
+            let matches = event.touches_context(&context);
+            async move { matches }
+        })
+    }
+
+    /// Parse and run a query-DSL expression against the in-memory nodes, e.g.
+    /// `resonance >= 0.8 AND context IN (scientific, water) AND archetype
+    /// CONTAINS "Flow" ORDER BY resonance DESC LIMIT 10`. See `crate::query`.
+    pub async fn query(&self, input: &str) -> Result<Vec<FractalNode>> {
+        let parsed = crate::query::parse(input)?;
+        let nodes = self.nodes.read().await;
+        let all: Vec<FractalNode> = nodes.values().cloned().collect();
+        Ok(crate::query::execute(&parsed, &all))
+    }
+
+    /// A stable-ordered page (oldest first, ties broken by id) of every
+    /// contribution, plus the total count so callers can compute `next`/`prev`.
+    pub async fn paginated_contributions(&self, offset: usize, limit: usize) -> (Vec<Contribution>, usize) {
+        let contributions = self.contributions.read().await;
+        let mut all: Vec<Contribution> = contributions.values().cloned().collect();
+        all.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+        let total = all.len();
+        let page = all.into_iter().skip(offset).take(limit).collect();
+        (page, total)
+    }
+
+    /// Same as `paginated_contributions`, filtered to one node.
+    pub async fn paginated_node_contributions(&self, node_id: &str, offset: usize, limit: usize) -> (Vec<Contribution>, usize) {
+        let contributions = self.contributions.read().await;
+        let mut matching: Vec<Contribution> = contributions.values().filter(|c| c.node_id == node_id).cloned().collect();
+        matching.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+        let total = matching.len();
+        let page = matching.into_iter().skip(offset).take(limit).collect();
+        (page, total)
+    }
+
+    /// Same as `paginated_contributions`, filtered to one user.
+    pub async fn paginated_user_contributions(&self, user_id: &str, offset: usize, limit: usize) -> (Vec<Contribution>, usize) {
+        let contributions = self.contributions.read().await;
+        let mut matching: Vec<Contribution> = contributions.values().filter(|c| c.user_id == user_id).cloned().collect();
+        matching.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+        let total = matching.len();
+        let page = matching.into_iter().skip(offset).take(limit).collect();
+        (page, total)
+    }
 }
 
 // ============================================================================
@@ -275,10 +779,24 @@ impl FractalStorage {
 // ============================================================================
 
 impl NodeStorage for FractalStorage {
-    async fn store_node(&self, node: &FractalNode) -> Result<()> {
+    async fn store_node(&self, node: &FractalNode) -> Result<StoreOutcome> {
         let mut nodes = self.nodes.write().await;
+        let is_new = !nodes.contains_key(&node.id);
+        let overflow = if is_new { (nodes.len() + 1).saturating_sub(self.config.max_nodes) } else { 0 };
+        let evicted_ids = if overflow > 0 { evict_nodes(&mut nodes, self.config.eviction, overflow) } else { Vec::new() };
         nodes.insert(node.id.clone(), node.clone());
-        
+        drop(nodes);
+
+        self.sync_resonance_signal(node).await;
+
+        for evicted_id in &evicted_ids {
+            self.forget_resonance_signal(evicted_id).await;
+            self.object_backend.delete(&format!("nodes/{}.json", evicted_id)).await?;
+            let should_checkpoint = self.oplog.append(Operation::DeleteNode(evicted_id.clone())).await?;
+            self.maybe_checkpoint(should_checkpoint).await?;
+            let _ = self.storage_events.send(StorageEvent::NodeDeleted { id: evicted_id.clone() });
+        }
+
         // Update stats
         let mut stats = self.stats.write().await;
         if node.fractal_level == 1 {
@@ -286,9 +804,22 @@ impl NodeStorage for FractalStorage {
         } else {
             stats.total_subnodes += 1;
         }
+        stats.evictions += evicted_ids.len() as u64;
         stats.last_updated = chrono::Utc::now();
-        
-        Ok(())
+        drop(stats);
+
+        let _ = self.storage_events.send(StorageEvent::NodeStored {
+            id: node.id.clone(),
+            fractal_level: node.fractal_level,
+            contexts: node.contexts.clone(),
+        });
+
+        self.object_backend.put(&format!("nodes/{}.json", node.id), serde_json::to_vec(node)?).await?;
+
+        let should_checkpoint = self.oplog.append(Operation::StoreNode(node.clone())).await?;
+        self.maybe_checkpoint(should_checkpoint).await?;
+
+        Ok(if evicted_ids.is_empty() { StoreOutcome::Stored } else { StoreOutcome::Evicted { evicted_ids } })
     }
 
     async fn get_node(&self, node_id: &str) -> Option<FractalNode> {
@@ -304,30 +835,79 @@ impl NodeStorage for FractalStorage {
     async fn delete_node(&self, node_id: &str) -> Result<()> {
         let mut nodes = self.nodes.write().await;
         nodes.remove(node_id);
-        Ok(())
+        drop(nodes);
+
+        self.forget_resonance_signal(node_id).await;
+
+        let _ = self.storage_events.send(StorageEvent::NodeDeleted { id: node_id.to_string() });
+
+        self.object_backend.delete(&format!("nodes/{}.json", node_id)).await?;
+
+        let should_checkpoint = self.oplog.append(Operation::DeleteNode(node_id.to_string())).await?;
+        self.maybe_checkpoint(should_checkpoint).await
     }
 }
 
 impl ContributionStorage for FractalStorage {
-    async fn store_contribution(&self, contribution: Contribution) -> Result<serde_json::Value> {
+    async fn store_contribution(&self, contribution: Contribution) -> Result<StoreOutcome> {
+        if contribution.resonance < self.config.resonance_threshold {
+            let mut stats = self.stats.write().await;
+            stats.contributions_rejected += 1;
+            drop(stats);
+            return Ok(StoreOutcome::RejectedBelowThreshold);
+        }
+
+        let content_hash = self.generate_content_hash(&contribution.content);
+
         let mut contributions = self.contributions.write().await;
+        let is_new = !contributions.contains_key(&contribution.id);
+        let overflow = if is_new { (contributions.len() + 1).saturating_sub(self.config.max_contributions) } else { 0 };
+        let evicted = if overflow > 0 { evict_contributions(&mut contributions, self.config.eviction, overflow) } else { Vec::new() };
         contributions.insert(contribution.id.clone(), contribution.clone());
-        
+        drop(contributions);
+
+        let evicted_ids: Vec<String> = evicted.iter().map(|c| c.id.clone()).collect();
+        for evicted in &evicted {
+            let evicted_hash = self.generate_content_hash(&evicted.content);
+            self.object_backend.delete(&format!("contributions/{}.json", evicted_hash)).await?;
+            let should_checkpoint = self.oplog.append(Operation::DeleteContribution(evicted.id.clone())).await?;
+            self.maybe_checkpoint(should_checkpoint).await?;
+            let _ = self.storage_events.send(StorageEvent::ContributionEvicted { id: evicted.id.clone() });
+        }
+
         // Update stats
         let mut stats = self.stats.write().await;
         stats.total_contributions += 1;
+        stats.evictions += evicted_ids.len() as u64;
         stats.last_updated = chrono::Utc::now();
-        
-        // Generate content hash
-        let content_hash = self.generate_content_hash(&contribution.content);
-        
-        Ok(serde_json::json!({
-            "id": contribution.id,
-            "content_hash": content_hash,
-            "node_id": contribution.node_id,
-            "resonance": contribution.resonance,
-            "timestamp": contribution.timestamp
-        }))
+        drop(stats);
+
+        // Extend the contribution Merkle tree and commit its new root. Recorded
+        // in `contribution_tree_leaves` too, so a later eviction of this same
+        // contribution from `contributions` doesn't also drop it from what
+        // `rebuild_contribution_tree` replays.
+        if let Some(leaf) = content_hash_to_leaf(&content_hash) {
+            self.contribution_tree_leaves.write().await.push(content_hash.clone());
+            let mut tree = self.contribution_tree.write().await;
+            tree.append(leaf);
+            let root = tree.root();
+            drop(tree);
+            self.stats.write().await.contribution_root = root.map(hex::encode);
+        }
+
+        // Notify any live subscribers; no receivers is not an error.
+        let _ = self.contribution_events.send(contribution.clone());
+        let _ = self.storage_events.send(StorageEvent::ContributionStored {
+            node_id: contribution.node_id.clone(),
+            resonance: contribution.resonance,
+        });
+
+        self.object_backend.put(&format!("contributions/{}.json", content_hash), serde_json::to_vec(&contribution)?).await?;
+
+        let should_checkpoint = self.oplog.append(Operation::StoreContribution(contribution.clone())).await?;
+        self.maybe_checkpoint(should_checkpoint).await?;
+
+        Ok(if evicted_ids.is_empty() { StoreOutcome::Stored } else { StoreOutcome::Evicted { evicted_ids } })
     }
 
     async fn get_contribution(&self, content_hash: &str) -> Option<Contribution> {
@@ -465,6 +1045,55 @@ impl StorageMetadata for FractalStorage {
 // UTILITY FUNCTIONS - Pure functions for data manipulation
 // ============================================================================
 
+/// Decode a `generate_content_hash` hex string back into the 32-byte leaf
+/// value the Merkle tree stores. `None` for anything that isn't a valid
+/// SHA-256 hex digest.
+fn content_hash_to_leaf(content_hash: &str) -> Option<crate::merkle::Hash> {
+    hex::decode(content_hash).ok()?.try_into().ok()
+}
+
+/// Order-independent key for the edge between two node ids, so `(a, b)` and
+/// `(b, a)` resolve to the same entry in `edge_strengths`.
+fn edge_key(a: &str, b: &str) -> String {
+    if a <= b { format!("{}|{}", a, b) } else { format!("{}|{}", b, a) }
+}
+
+/// Remove `count` nodes from `nodes` chosen by `policy`, returning their ids.
+/// Used by `store_node` to stay within `StorageConfig::max_nodes`.
+fn evict_nodes(nodes: &mut HashMap<String, FractalNode>, policy: Policy, count: usize) -> Vec<String> {
+    let mut candidates: Vec<&FractalNode> = nodes.values().collect();
+    match policy {
+        Policy::LowestResonanceFirst => {
+            candidates.sort_by(|a, b| a.resonance.partial_cmp(&b.resonance).unwrap_or(std::cmp::Ordering::Equal))
+        }
+        Policy::OldestFirst => candidates.sort_by(|a, b| a.created_at.cmp(&b.created_at)),
+    }
+
+    let ids: Vec<String> = candidates.into_iter().take(count).map(|n| n.id.clone()).collect();
+    for id in &ids {
+        nodes.remove(id);
+    }
+    ids
+}
+
+/// Remove `count` contributions from `contributions` chosen by `policy`,
+/// returning the removed contributions (the caller needs their content to
+/// clean up `object_backend`, which keys contributions by content hash
+/// rather than id). Used by `store_contribution` to stay within
+/// `StorageConfig::max_contributions`.
+fn evict_contributions(contributions: &mut HashMap<String, Contribution>, policy: Policy, count: usize) -> Vec<Contribution> {
+    let mut candidates: Vec<&Contribution> = contributions.values().collect();
+    match policy {
+        Policy::LowestResonanceFirst => {
+            candidates.sort_by(|a, b| a.resonance.partial_cmp(&b.resonance).unwrap_or(std::cmp::Ordering::Equal))
+        }
+        Policy::OldestFirst => candidates.sort_by(|a, b| a.timestamp.cmp(&b.timestamp)),
+    }
+
+    let ids: Vec<String> = candidates.into_iter().take(count).map(|c| c.id.clone()).collect();
+    ids.into_iter().filter_map(|id| contributions.remove(&id)).collect()
+}
+
 pub fn filter_nodes_by_resonance(nodes: &[FractalNode], min_resonance: f64) -> Vec<FractalNode> {
     nodes.iter()
         .filter(|node| node.resonance >= min_resonance)
@@ -0,0 +1,98 @@
+//! Property-based verification of the `Resonant` contracts on `FractalNode`.
+//!
+//! `can_resonate_with` and `resonance_strength` have implicit invariants that
+//! are never checked anywhere else in the crate: symmetry, reflexivity, the
+//! `[0, 1]` range, and the coincidence of the 10.0 cutoff with strength
+//! dropping to zero. This module only compiles under `cfg(test)` and exists
+//! to catch regressions if the frequency scaling or the cutoff constant ever
+//! changes.
+
+#![cfg(test)]
+
+use crate::models::{FractalNode, FlowState, Resonant};
+use proptest::prelude::*;
+
+/// Bound generated resonance to a sane range; the domain model treats
+/// resonance as a `[0, 1]`-ish dial, and values far outside that aren't
+/// meaningful inputs to verify against.
+fn resonance_strategy() -> impl Strategy<Value = f64> {
+    0.0f64..=2.0
+}
+
+fn flow_state_strategy() -> impl Strategy<Value = FlowState> {
+    prop_oneof![
+        Just(FlowState::Solid),
+        Just(FlowState::Liquid),
+        Just(FlowState::Gas),
+        Just(FlowState::Plasma),
+        Just(FlowState::Colloidal),
+        Just(FlowState::Crystalline),
+        Just(FlowState::Living),
+        Just(FlowState::Wave),
+    ]
+}
+
+fn fractal_node_strategy() -> impl Strategy<Value = FractalNode> {
+    (any::<u32>(), flow_state_strategy(), resonance_strategy(), 1u32..=3).prop_map(
+        |(seed, water_state, resonance, fractal_level)| {
+            FractalNode::new(
+                format!("codex:test-{}", seed),
+                format!("Test-{}", seed),
+                water_state,
+                vec!["Test".to_string()],
+                resonance,
+                fractal_level,
+                None,
+            )
+        },
+    )
+}
+
+proptest! {
+    #[test]
+    fn can_resonate_with_is_symmetric(a in fractal_node_strategy(), b in fractal_node_strategy()) {
+        prop_assert_eq!(a.can_resonate_with(&b), b.can_resonate_with(&a));
+    }
+
+    #[test]
+    fn can_resonate_with_is_reflexive(n in fractal_node_strategy()) {
+        prop_assert!(n.can_resonate_with(&n));
+    }
+
+    #[test]
+    fn resonance_strength_is_bounded(a in fractal_node_strategy(), b in fractal_node_strategy()) {
+        let strength = a.resonance_strength(&b);
+        prop_assert!(strength >= 0.0 && strength <= 1.0);
+    }
+
+    #[test]
+    fn resonance_strength_zero_iff_cannot_resonate(a in fractal_node_strategy(), b in fractal_node_strategy()) {
+        let strength = a.resonance_strength(&b);
+        prop_assert_eq!(strength == 0.0, !a.can_resonate_with(&b));
+    }
+
+    /// Within the 10.0 cutoff in `can_resonate_with`, `resonance_strength`
+    /// must not increase as frequency distance grows. At the cutoff it drops
+    /// straight to 0 (see `resonance_strength_zero_iff_cannot_resonate`).
+    #[test]
+    fn resonance_strength_decays_monotonically_with_frequency_distance(
+        base in fractal_node_strategy(),
+        delta_a in 0.0f64..9.0,
+        delta_b in 0.0f64..9.0,
+    ) {
+        let (closer, farther) = if delta_a <= delta_b { (delta_a, delta_b) } else { (delta_b, delta_a) };
+
+        let near = FractalNode::new(
+            "codex:near".to_string(), "Near".to_string(), FlowState::Liquid,
+            vec![], (base.resonance_frequency() + closer) / 100.0, 1, None,
+        );
+        let far = FractalNode::new(
+            "codex:far".to_string(), "Far".to_string(), FlowState::Liquid,
+            vec![], (base.resonance_frequency() + farther) / 100.0, 1, None,
+        );
+
+        if base.can_resonate_with(&near) && base.can_resonate_with(&far) {
+            prop_assert!(base.resonance_strength(&near) >= base.resonance_strength(&far));
+        }
+    }
+}
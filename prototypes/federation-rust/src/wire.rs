@@ -0,0 +1,518 @@
+use crate::models::{ContextType, Contribution, FlowState, FractalNode, ScientificContext, SymbolicContext, WaterContext};
+use chrono::{TimeZone, Utc};
+use std::collections::HashMap;
+use std::fmt;
+
+// ============================================================================
+// PROTOBUF WIRE FORMAT - Compact encoding for syncing fractal trees
+// ============================================================================
+//
+// JSON via serde is fine for humans but too heavy for syncing large fractal
+// trees between instances. This module defines `.proto`-shaped message types
+// and a `RustType<Proto>` conversion trait (Materialize's pattern): each core
+// struct knows how to become its wire `Proto` twin and how to reject a
+// malformed one, rather than unwrapping and panicking on bad input.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TryFromProtoError {
+    MissingField(&'static str),
+    UnknownEnumVariant { type_name: &'static str, discriminant: i32 },
+}
+
+impl TryFromProtoError {
+    pub fn missing_field(field: &'static str) -> Self {
+        TryFromProtoError::MissingField(field)
+    }
+
+    pub fn unknown_enum_variant(type_name: &'static str, discriminant: i32) -> Self {
+        TryFromProtoError::UnknownEnumVariant { type_name, discriminant }
+    }
+}
+
+impl fmt::Display for TryFromProtoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryFromProtoError::MissingField(field) => write!(f, "missing required proto field: {}", field),
+            TryFromProtoError::UnknownEnumVariant { type_name, discriminant } => {
+                write!(f, "unknown enum discriminant {} for {}", discriminant, type_name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TryFromProtoError {}
+
+/// Converts a Rust type to and from its protobuf wire representation.
+pub trait RustType<Proto> {
+    fn into_proto(&self) -> Proto;
+    fn from_proto(proto: Proto) -> Result<Self, TryFromProtoError>
+    where
+        Self: Sized;
+}
+
+// ============================================================================
+// PROTO MESSAGE SHAPES
+// ============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProtoFractalNode {
+    pub id: String,
+    pub name: String,
+    pub water_state: i32,
+    pub archetype: Vec<String>,
+    pub resonance: f64,
+    pub fractal_level: u32,
+    pub contexts: Vec<ProtoContextType>,
+    pub parent_id: Option<String>,
+    pub created_at_unix_millis: i64,
+    pub updated_at_unix_millis: i64,
+    pub metadata: Vec<ProtoMetadataEntry>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProtoContribution {
+    pub id: String,
+    pub node_id: String,
+    pub user_id: String,
+    pub content: String,
+    pub resonance: f64,
+    pub timestamp_unix_millis: i64,
+    pub fractal_context: Option<ProtoContextType>,
+    pub metadata: Vec<ProtoMetadataEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtoMetadataEntry {
+    pub key: String,
+    pub value_json: String,
+}
+
+/// Mirrors the recursive `ContextType::Hybrid(Vec<ContextType>)` as a `oneof`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProtoContextType {
+    Scientific(i32),
+    Symbolic(i32),
+    Water(i32),
+    Hybrid(Vec<ProtoContextType>),
+}
+
+impl RustType<i32> for FlowState {
+    fn into_proto(&self) -> i32 {
+        match self {
+            FlowState::Solid => 0,
+            FlowState::Liquid => 1,
+            FlowState::Gas => 2,
+            FlowState::Plasma => 3,
+            FlowState::Colloidal => 4,
+            FlowState::Crystalline => 5,
+            FlowState::Living => 6,
+            FlowState::Wave => 7,
+        }
+    }
+
+    fn from_proto(proto: i32) -> Result<Self, TryFromProtoError> {
+        match proto {
+            0 => Ok(FlowState::Solid),
+            1 => Ok(FlowState::Liquid),
+            2 => Ok(FlowState::Gas),
+            3 => Ok(FlowState::Plasma),
+            4 => Ok(FlowState::Colloidal),
+            5 => Ok(FlowState::Crystalline),
+            6 => Ok(FlowState::Living),
+            7 => Ok(FlowState::Wave),
+            other => Err(TryFromProtoError::unknown_enum_variant("FlowState", other)),
+        }
+    }
+}
+
+impl RustType<ProtoContextType> for ContextType {
+    fn into_proto(&self) -> ProtoContextType {
+        match self {
+            ContextType::Scientific(s) => ProtoContextType::Scientific(scientific_to_proto(s)),
+            ContextType::Symbolic(s) => ProtoContextType::Symbolic(symbolic_to_proto(s)),
+            ContextType::Water(w) => ProtoContextType::Water(water_to_proto(w)),
+            ContextType::Hybrid(children) => {
+                ProtoContextType::Hybrid(children.iter().map(|c| c.into_proto()).collect())
+            }
+        }
+    }
+
+    fn from_proto(proto: ProtoContextType) -> Result<Self, TryFromProtoError> {
+        match proto {
+            ProtoContextType::Scientific(d) => Ok(ContextType::Scientific(scientific_from_proto(d)?)),
+            ProtoContextType::Symbolic(d) => Ok(ContextType::Symbolic(symbolic_from_proto(d)?)),
+            ProtoContextType::Water(d) => Ok(ContextType::Water(water_from_proto(d)?)),
+            ProtoContextType::Hybrid(children) => {
+                let converted = children
+                    .into_iter()
+                    .map(ContextType::from_proto)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ContextType::Hybrid(converted))
+            }
+        }
+    }
+}
+
+fn scientific_to_proto(s: &ScientificContext) -> i32 {
+    match s {
+        ScientificContext::Empirical => 0,
+        ScientificContext::Theoretical => 1,
+        ScientificContext::Experimental => 2,
+    }
+}
+
+fn scientific_from_proto(d: i32) -> Result<ScientificContext, TryFromProtoError> {
+    match d {
+        0 => Ok(ScientificContext::Empirical),
+        1 => Ok(ScientificContext::Theoretical),
+        2 => Ok(ScientificContext::Experimental),
+        other => Err(TryFromProtoError::unknown_enum_variant("ScientificContext", other)),
+    }
+}
+
+fn symbolic_to_proto(s: &SymbolicContext) -> i32 {
+    match s {
+        SymbolicContext::Archetypal => 0,
+        SymbolicContext::Cultural => 1,
+        SymbolicContext::Personal => 2,
+    }
+}
+
+fn symbolic_from_proto(d: i32) -> Result<SymbolicContext, TryFromProtoError> {
+    match d {
+        0 => Ok(SymbolicContext::Archetypal),
+        1 => Ok(SymbolicContext::Cultural),
+        2 => Ok(SymbolicContext::Personal),
+        other => Err(TryFromProtoError::unknown_enum_variant("SymbolicContext", other)),
+    }
+}
+
+fn water_to_proto(w: &WaterContext) -> i32 {
+    match w {
+        WaterContext::Phase => 0,
+        WaterContext::Flow => 1,
+        WaterContext::Coherence => 2,
+    }
+}
+
+fn water_from_proto(d: i32) -> Result<WaterContext, TryFromProtoError> {
+    match d {
+        0 => Ok(WaterContext::Phase),
+        1 => Ok(WaterContext::Flow),
+        2 => Ok(WaterContext::Coherence),
+        other => Err(TryFromProtoError::unknown_enum_variant("WaterContext", other)),
+    }
+}
+
+impl RustType<ProtoFractalNode> for FractalNode {
+    fn into_proto(&self) -> ProtoFractalNode {
+        ProtoFractalNode {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            water_state: self.water_state.into_proto(),
+            archetype: self.archetype.clone(),
+            resonance: self.resonance,
+            fractal_level: self.fractal_level,
+            contexts: self.contexts.iter().map(|c| c.into_proto()).collect(),
+            parent_id: self.parent_id.clone(),
+            created_at_unix_millis: self.created_at.timestamp_millis(),
+            updated_at_unix_millis: self.updated_at.timestamp_millis(),
+            metadata: metadata_to_proto(&self.metadata),
+        }
+    }
+
+    fn from_proto(proto: ProtoFractalNode) -> Result<Self, TryFromProtoError> {
+        Ok(FractalNode {
+            id: proto.id,
+            name: proto.name,
+            water_state: FlowState::from_proto(proto.water_state)?,
+            archetype: proto.archetype,
+            resonance: proto.resonance,
+            fractal_level: proto.fractal_level,
+            contexts: proto
+                .contexts
+                .into_iter()
+                .map(ContextType::from_proto)
+                .collect::<Result<Vec<_>, _>>()?,
+            parent_id: proto.parent_id,
+            created_at: millis_to_utc(proto.created_at_unix_millis),
+            updated_at: millis_to_utc(proto.updated_at_unix_millis),
+            metadata: metadata_from_proto(proto.metadata),
+            schema_version: crate::schema::CURRENT_SCHEMA_VERSION,
+        })
+    }
+}
+
+impl RustType<ProtoContribution> for Contribution {
+    fn into_proto(&self) -> ProtoContribution {
+        ProtoContribution {
+            id: self.id.clone(),
+            node_id: self.node_id.clone(),
+            user_id: self.user_id.clone(),
+            content: self.content.clone(),
+            resonance: self.resonance,
+            timestamp_unix_millis: self.timestamp.timestamp_millis(),
+            fractal_context: self.fractal_context.as_ref().map(|c| c.into_proto()),
+            metadata: metadata_to_proto(&self.metadata),
+        }
+    }
+
+    fn from_proto(proto: ProtoContribution) -> Result<Self, TryFromProtoError> {
+        Ok(Contribution {
+            id: proto.id,
+            node_id: proto.node_id,
+            user_id: proto.user_id,
+            content: proto.content,
+            resonance: proto.resonance,
+            timestamp: millis_to_utc(proto.timestamp_unix_millis),
+            fractal_context: proto.fractal_context.map(ContextType::from_proto).transpose()?,
+            metadata: metadata_from_proto(proto.metadata),
+            schema_version: crate::schema::CURRENT_SCHEMA_VERSION,
+        })
+    }
+}
+
+fn millis_to_utc(millis: i64) -> chrono::DateTime<Utc> {
+    Utc.timestamp_millis_opt(millis).single().unwrap_or_else(Utc::now)
+}
+
+fn metadata_to_proto(metadata: &HashMap<String, serde_json::Value>) -> Vec<ProtoMetadataEntry> {
+    metadata
+        .iter()
+        .map(|(key, value)| ProtoMetadataEntry { key: key.clone(), value_json: value.to_string() })
+        .collect()
+}
+
+fn metadata_from_proto(entries: Vec<ProtoMetadataEntry>) -> HashMap<String, serde_json::Value> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let value = serde_json::from_str(&entry.value_json).unwrap_or(serde_json::Value::Null);
+            (entry.key, value)
+        })
+        .collect()
+}
+
+// ============================================================================
+// WIRE ENCODING - Minimal positional varint/length-delimited codec (fields are
+// written in a fixed order with no protobuf field tags), so nodes can be
+// streamed and diffed without JSON's overhead.
+// ============================================================================
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            break;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Result<u64, TryFromProtoError> {
+    let mut result: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *buf.get(*pos).ok_or(TryFromProtoError::MissingField("varint"))?;
+        *pos += 1;
+        result |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+    }
+    Ok(result)
+}
+
+fn write_bytes_field(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn read_bytes_field<'a>(buf: &'a [u8], pos: &mut usize) -> Result<&'a [u8], TryFromProtoError> {
+    let len = read_varint(buf, pos)? as usize;
+    let start = *pos;
+    let end = start.checked_add(len).ok_or(TryFromProtoError::MissingField("length"))?;
+    let slice = buf.get(start..end).ok_or(TryFromProtoError::MissingField("bytes"))?;
+    *pos = end;
+    Ok(slice)
+}
+
+/// The oneof's three leaf variants and the recursive `Hybrid` case each get a
+/// tag byte so `read_context` can dispatch without guessing.
+const CONTEXT_TAG_SCIENTIFIC: u8 = 0;
+const CONTEXT_TAG_SYMBOLIC: u8 = 1;
+const CONTEXT_TAG_WATER: u8 = 2;
+const CONTEXT_TAG_HYBRID: u8 = 3;
+
+fn write_context(buf: &mut Vec<u8>, context: &ProtoContextType) {
+    match context {
+        ProtoContextType::Scientific(d) => {
+            buf.push(CONTEXT_TAG_SCIENTIFIC);
+            write_varint(buf, *d as u64);
+        }
+        ProtoContextType::Symbolic(d) => {
+            buf.push(CONTEXT_TAG_SYMBOLIC);
+            write_varint(buf, *d as u64);
+        }
+        ProtoContextType::Water(d) => {
+            buf.push(CONTEXT_TAG_WATER);
+            write_varint(buf, *d as u64);
+        }
+        ProtoContextType::Hybrid(children) => {
+            buf.push(CONTEXT_TAG_HYBRID);
+            write_varint(buf, children.len() as u64);
+            for child in children {
+                write_context(buf, child);
+            }
+        }
+    }
+}
+
+fn read_context(buf: &[u8], pos: &mut usize) -> Result<ProtoContextType, TryFromProtoError> {
+    let tag = *buf.get(*pos).ok_or(TryFromProtoError::MissingField("context_tag"))?;
+    *pos += 1;
+    match tag {
+        CONTEXT_TAG_SCIENTIFIC => Ok(ProtoContextType::Scientific(read_varint(buf, pos)? as i32)),
+        CONTEXT_TAG_SYMBOLIC => Ok(ProtoContextType::Symbolic(read_varint(buf, pos)? as i32)),
+        CONTEXT_TAG_WATER => Ok(ProtoContextType::Water(read_varint(buf, pos)? as i32)),
+        CONTEXT_TAG_HYBRID => {
+            let count = read_varint(buf, pos)?;
+            let mut children = Vec::with_capacity(count as usize);
+            for _ in 0..count {
+                children.push(read_context(buf, pos)?);
+            }
+            Ok(ProtoContextType::Hybrid(children))
+        }
+        other => Err(TryFromProtoError::unknown_enum_variant("ProtoContextType", other as i32)),
+    }
+}
+
+impl ProtoFractalNode {
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_bytes_field(&mut buf, self.id.as_bytes());
+        write_bytes_field(&mut buf, self.name.as_bytes());
+        write_varint(&mut buf, self.water_state as u64);
+        write_varint(&mut buf, self.resonance.to_bits());
+        write_varint(&mut buf, self.fractal_level as u64);
+        write_varint(&mut buf, self.contexts.len() as u64);
+        for context in &self.contexts {
+            write_context(&mut buf, context);
+        }
+        let json = serde_json::json!({
+            "archetype": self.archetype,
+            "parent_id": self.parent_id,
+            "created_at_unix_millis": self.created_at_unix_millis,
+            "updated_at_unix_millis": self.updated_at_unix_millis,
+            "metadata": self.metadata.iter().map(|e| (e.key.clone(), e.value_json.clone())).collect::<HashMap<_, _>>(),
+        });
+        write_bytes_field(&mut buf, json.to_string().as_bytes());
+        buf
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<Self, TryFromProtoError> {
+        let mut pos = 0;
+        let id = String::from_utf8_lossy(read_bytes_field(buf, &mut pos)?).into_owned();
+        let name = String::from_utf8_lossy(read_bytes_field(buf, &mut pos)?).into_owned();
+        let water_state = read_varint(buf, &mut pos)? as i32;
+        let resonance = f64::from_bits(read_varint(buf, &mut pos)?);
+        let fractal_level = read_varint(buf, &mut pos)? as u32;
+        let context_count = read_varint(buf, &mut pos)?;
+        let mut contexts = Vec::with_capacity(context_count as usize);
+        for _ in 0..context_count {
+            contexts.push(read_context(buf, &mut pos)?);
+        }
+        let tail = read_bytes_field(buf, &mut pos)?;
+        let parsed: serde_json::Value =
+            serde_json::from_slice(tail).map_err(|_| TryFromProtoError::MissingField("tail"))?;
+
+        let archetype = parsed
+            .get("archetype")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|s| s.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let parent_id = parsed.get("parent_id").and_then(|v| v.as_str()).map(String::from);
+        let created_at_unix_millis = parsed.get("created_at_unix_millis").and_then(|v| v.as_i64()).unwrap_or(0);
+        let updated_at_unix_millis = parsed.get("updated_at_unix_millis").and_then(|v| v.as_i64()).unwrap_or(0);
+        let metadata = parsed
+            .get("metadata")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| ProtoMetadataEntry { key: k.clone(), value_json: v.as_str().unwrap_or_default().to_string() })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(ProtoFractalNode {
+            id,
+            name,
+            water_state,
+            archetype,
+            resonance,
+            fractal_level,
+            contexts,
+            parent_id,
+            created_at_unix_millis,
+            updated_at_unix_millis,
+            metadata,
+        })
+    }
+}
+
+impl ProtoContribution {
+    pub fn encode_to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_bytes_field(&mut buf, self.id.as_bytes());
+        write_bytes_field(&mut buf, self.node_id.as_bytes());
+        write_bytes_field(&mut buf, self.user_id.as_bytes());
+        write_bytes_field(&mut buf, self.content.as_bytes());
+        write_varint(&mut buf, self.resonance.to_bits());
+        match &self.fractal_context {
+            Some(context) => {
+                buf.push(1);
+                write_context(&mut buf, context);
+            }
+            None => buf.push(0),
+        }
+        let json = serde_json::json!({
+            "timestamp_unix_millis": self.timestamp_unix_millis,
+            "metadata": self.metadata.iter().map(|e| (e.key.clone(), e.value_json.clone())).collect::<HashMap<_, _>>(),
+        });
+        write_bytes_field(&mut buf, json.to_string().as_bytes());
+        buf
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<Self, TryFromProtoError> {
+        let mut pos = 0;
+        let id = String::from_utf8_lossy(read_bytes_field(buf, &mut pos)?).into_owned();
+        let node_id = String::from_utf8_lossy(read_bytes_field(buf, &mut pos)?).into_owned();
+        let user_id = String::from_utf8_lossy(read_bytes_field(buf, &mut pos)?).into_owned();
+        let content = String::from_utf8_lossy(read_bytes_field(buf, &mut pos)?).into_owned();
+        let resonance = f64::from_bits(read_varint(buf, &mut pos)?);
+
+        let has_context = *buf.get(pos).ok_or(TryFromProtoError::MissingField("fractal_context_flag"))?;
+        pos += 1;
+        let fractal_context = if has_context == 1 { Some(read_context(buf, &mut pos)?) } else { None };
+
+        let tail = read_bytes_field(buf, &mut pos)?;
+        let parsed: serde_json::Value =
+            serde_json::from_slice(tail).map_err(|_| TryFromProtoError::MissingField("tail"))?;
+
+        let timestamp_unix_millis = parsed.get("timestamp_unix_millis").and_then(|v| v.as_i64()).unwrap_or(0);
+        let metadata = parsed
+            .get("metadata")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| ProtoMetadataEntry { key: k.clone(), value_json: v.as_str().unwrap_or_default().to_string() })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(ProtoContribution { id, node_id, user_id, content, resonance, timestamp_unix_millis, fractal_context, metadata })
+    }
+}